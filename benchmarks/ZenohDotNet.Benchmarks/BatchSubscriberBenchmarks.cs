@@ -0,0 +1,108 @@
+using BenchmarkDotNet.Attributes;
+using BenchmarkDotNet.Jobs;
+using ZenohDotNet.Client;
+
+namespace ZenohDotNet.Benchmarks;
+
+/// <summary>
+/// Benchmarks comparing per-sample delivery with batched delivery on a high-rate key expression.
+/// </summary>
+[SimpleJob(RuntimeMoniker.Net80)]
+[MemoryDiagnoser]
+[RankColumn]
+public class BatchSubscriberBenchmarks
+{
+    private Session _pubSession = null!;
+    private Session _subSession = null!;
+    private Publisher _perSamplePublisher = null!;
+    private Publisher _batchedPublisher = null!;
+    private Subscriber _subscriber = null!;
+    private BatchSubscriber _batchSubscriber = null!;
+    private byte[] _payload = null!;
+    private int _receivedCount;
+    private TaskCompletionSource<bool>? _receiveComplete;
+
+    [Params(1000, 10000)]
+    public int MessageCount { get; set; }
+
+    [Params(16, 256)]
+    public int BatchSize { get; set; }
+
+    [GlobalSetup]
+    public async Task Setup()
+    {
+        _pubSession = await Session.OpenAsync();
+        _subSession = await Session.OpenAsync();
+
+        var perSampleKey = $"benchmark/batch/{Guid.NewGuid()}";
+        var batchedKey = $"benchmark/batch/{Guid.NewGuid()}";
+
+        _subscriber = await _subSession.DeclareSubscriberAsync(perSampleKey, _ => OnReceived(1));
+        _batchSubscriber = await _subSession.DeclareBatchSubscriberAsync(
+            batchedKey,
+            BatchSize,
+            TimeSpan.FromMilliseconds(1),
+            samples => OnReceived(samples.Count));
+
+        _perSamplePublisher = await _pubSession.DeclarePublisherAsync(perSampleKey);
+        _batchedPublisher = await _pubSession.DeclarePublisherAsync(batchedKey);
+
+        _payload = new byte[64];
+        Random.Shared.NextBytes(_payload);
+
+        // Warmup
+        await Task.Delay(200);
+    }
+
+    [GlobalCleanup]
+    public async Task Cleanup()
+    {
+        await _perSamplePublisher.DisposeAsync();
+        await _batchedPublisher.DisposeAsync();
+        await _subscriber.DisposeAsync();
+        await _batchSubscriber.DisposeAsync();
+        await _pubSession.DisposeAsync();
+        await _subSession.DisposeAsync();
+    }
+
+    [IterationSetup]
+    public void IterationSetup()
+    {
+        _receivedCount = 0;
+        _receiveComplete = new TaskCompletionSource<bool>();
+    }
+
+    [Benchmark(Baseline = true)]
+    public async Task PerSample()
+    {
+        for (int i = 0; i < MessageCount; i++)
+        {
+            await _perSamplePublisher.PutAsync(_payload);
+        }
+        await WaitForMessages();
+    }
+
+    [Benchmark]
+    public async Task Batched()
+    {
+        for (int i = 0; i < MessageCount; i++)
+        {
+            await _batchedPublisher.PutAsync(_payload);
+        }
+        await WaitForMessages();
+    }
+
+    private void OnReceived(int count)
+    {
+        if (Interlocked.Add(ref _receivedCount, count) >= MessageCount)
+        {
+            _receiveComplete?.TrySetResult(true);
+        }
+    }
+
+    private async Task WaitForMessages()
+    {
+        var timeout = Task.Delay(TimeSpan.FromSeconds(30));
+        await Task.WhenAny(_receiveComplete!.Task, timeout);
+    }
+}
@@ -34,6 +34,7 @@ public class QueryBenchmarks
         _queryable = await _queryableSession.DeclareQueryableAsync(_keyExpr, query =>
         {
             query.Reply(_keyExpr, _replyPayload);
+            query.Dispose();
         });
 
         // Warmup
@@ -1,17 +1,29 @@
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use std::cell::RefCell;
+use std::collections::{BTreeSet, HashSet, VecDeque};
 use std::ffi::{c_char, c_void, CStr, CString};
 use std::panic;
 use std::ptr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::runtime::Runtime;
+use tokio::sync::Notify;
 use zenoh::config::Config;
+use zenoh::handlers::{FifoChannel, FifoChannelHandler, RingChannel, RingChannelHandler};
+use zenoh::key_expr::format::KeFormat;
+use zenoh::key_expr::{keyexpr, KeyExpr};
 use zenoh::pubsub::{Publisher, Subscriber};
 use zenoh::qos::{CongestionControl, Priority};
-use zenoh::query::{Query, Queryable};
-use zenoh::sample::{Sample, SampleKind};
+use zenoh::query::{
+    ConsolidationMode, Parameters, Query, QueryTarget, Queryable, Reply, ReplyError, Selector,
+    TimeBound, TimeRange, ZenohParameters,
+};
+use zenoh::sample::{Locality, Sample, SampleKind};
+use zenoh::time::{Timestamp, TimestampId, NTP64};
 use zenoh::Session;
 use zenoh::liveliness::LivelinessToken;
+use zenoh::matching::{MatchingListener, MatchingStatus};
 
 // Global Tokio runtime for async operations
 static RUNTIME: Lazy<Runtime> = Lazy::new(|| {
@@ -76,6 +88,7 @@ where
 
 thread_local! {
     static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+    static VIEW_SCRATCH: RefCell<ViewScratch> = const { RefCell::new(ViewScratch::new()) };
 }
 
 fn set_error(msg: impl ToString) {
@@ -107,6 +120,12 @@ pub extern "C" fn zenoh_last_error() -> *const c_char {
 
 struct SessionWrapper {
     session: Arc<Session>,
+    /// Queue that callbacks of this session go through when dispatching is enabled.
+    dispatch: Arc<DispatchQueue>,
+    /// Dispatcher for asynchronous gets, whose queued callbacks are skipped once
+    /// the session is closed.
+    dispatcher: Dispatcher,
+    _dispatch_guard: Arc<()>,
 }
 
 struct PublisherWrapper {
@@ -114,18 +133,122 @@ struct PublisherWrapper {
     /// Holds a reference to the session to ensure it outlives the publisher.
     /// This prevents undefined behavior from the transmute to 'static.
     _session: Arc<Session>,
+    dispatch: Arc<DispatchQueue>,
 }
 
 struct SubscriberWrapper {
     _subscriber: Arc<Subscriber<()>>,
+    /// Keeps queued callbacks of this subscriber deliverable; see Dispatcher.
+    _dispatch_guard: Arc<()>,
 }
 
 struct QueryableWrapper {
     _queryable: Arc<Queryable<()>>,
+    /// Queries handed out to the callback that may not have been finalized yet.
+    pending_queries: Arc<Mutex<Vec<Weak<QueryWrapper>>>>,
+    _dispatch_guard: Arc<()>,
 }
 
+impl Drop for QueryableWrapper {
+    /// Finalizes queries the caller never finalized so requesters are not left waiting.
+    /// Their handles stay valid and must still be released with zenoh_query_finalize;
+    /// their selector, encoding, payload and attachment remain readable until then.
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending_queries.lock() {
+            for query in pending.drain(..).filter_map(|q| q.upgrade()) {
+                query.finalize_keep_data();
+            }
+        }
+    }
+}
+
+/// Query handle shared between the caller and its queryable.
+/// Handed out to the caller as a raw `Arc` pointer.
 struct QueryWrapper {
-    query: Box<Query>,
+    /// The pending query; None once it has been finalized.
+    /// The final reply message is sent when the last clone of the Query is dropped.
+    query: Mutex<Option<Query>>,
+    /// Contiguous copies of the query payload and attachment, created on first access
+    /// so the pointers returned to the caller stay valid until the handle is freed.
+    payload: OnceCell<Option<Vec<u8>>>,
+    attachment: OnceCell<Option<Vec<u8>>>,
+    /// Selector and encoding, copied when the query is finalized while the handle stays in use.
+    info: OnceCell<QueryInfo>,
+    /// Timestamp id of the queryable's session, used for reply timestamps without an id.
+    timestamp_id: TimestampId,
+}
+
+/// Owned copy of the selector and encoding of a query.
+#[derive(Clone)]
+struct QueryInfo {
+    key_expr: String,
+    parameters: Parameters<'static>,
+    encoding: Option<String>,
+}
+
+impl QueryInfo {
+    fn new(query: &Query) -> Self {
+        QueryInfo {
+            key_expr: query.key_expr().as_str().to_string(),
+            parameters: Parameters::from(query.parameters().as_str().to_string()),
+            encoding: query.encoding().map(|e| e.to_string()),
+        }
+    }
+}
+
+impl QueryWrapper {
+    /// Returns a clone of the query, or an error if it has already been finalized.
+    fn get(&self) -> Result<Query, String> {
+        self.query
+            .lock()
+            .map_err(|_| "Query lock poisoned".to_string())?
+            .clone()
+            .ok_or_else(|| "Query has already been finalized".to_string())
+    }
+
+    fn finalize(&self) {
+        if let Ok(mut query) = self.query.lock() {
+            query.take();
+        }
+    }
+
+    /// Finalizes the query while the handle stays in use, copying the selector,
+    /// encoding, payload and attachment first so their accessors keep working.
+    fn finalize_keep_data(&self) {
+        let query = self.query.lock().ok().and_then(|mut query| query.take());
+        if let Some(query) = query {
+            self.info.get_or_init(|| QueryInfo::new(&query));
+            self.payload.get_or_init(|| query.payload().map(|p| p.to_bytes().into_owned()));
+            self.attachment.get_or_init(|| query.attachment().map(|a| a.to_bytes().into_owned()));
+        }
+    }
+
+    /// Returns the selector and encoding, from the copy taken by finalize_keep_data
+    /// or else from the pending query.
+    fn info(&self) -> Result<std::borrow::Cow<'_, QueryInfo>, String> {
+        if let Some(info) = self.info.get() {
+            return Ok(std::borrow::Cow::Borrowed(info));
+        }
+        Ok(std::borrow::Cow::Owned(QueryInfo::new(&self.get()?)))
+    }
+
+    /// Returns the payload copy, creating it from the pending query on first access.
+    fn payload(&self) -> Result<&Option<Vec<u8>>, String> {
+        if let Some(payload) = self.payload.get() {
+            return Ok(payload);
+        }
+        let query = self.get()?;
+        Ok(self.payload.get_or_init(|| query.payload().map(|p| p.to_bytes().into_owned())))
+    }
+
+    /// Returns the attachment copy, creating it from the pending query on first access.
+    fn attachment(&self) -> Result<&Option<Vec<u8>>, String> {
+        if let Some(attachment) = self.attachment.get() {
+            return Ok(attachment);
+        }
+        let query = self.get()?;
+        Ok(self.attachment.get_or_init(|| query.attachment().map(|a| a.to_bytes().into_owned())))
+    }
 }
 
 struct LivelinessTokenWrapper {
@@ -137,6 +260,152 @@ struct QuerierWrapper {
     /// Holds a reference to the session to ensure it outlives the querier.
     /// This prevents undefined behavior from the transmute to 'static.
     _session: Arc<Session>,
+    dispatch: Arc<DispatchQueue>,
+    /// Dispatcher for the replies of this querier's gets.
+    dispatcher: Dispatcher,
+    _dispatch_guard: Arc<()>,
+}
+
+/// Alive-set bookkeeping of a presence tracker.
+struct PresenceState {
+    alive: BTreeSet<String>,
+    /// True until the initial liveliness query has completed.
+    syncing: bool,
+    /// Keys removed by the live stream while syncing, so that stale replies
+    /// from the initial query do not resurrect them.
+    removed_during_sync: HashSet<String>,
+}
+
+/// Presence tracking shared between the tracker handle, its subscriber callback and
+/// the initial liveliness query.
+struct PresenceTracker {
+    state: Mutex<PresenceState>,
+    /// Held across each state change and the dispatch of its event, so that events
+    /// are delivered in the order the state changed. The query functions only take
+    /// the state lock and can therefore be called from the callback.
+    delivery: Mutex<()>,
+    dispatcher: Dispatcher,
+    callback: ZenohLivelinessCallback,
+    context: usize,
+}
+
+struct PresenceTrackerWrapper {
+    _subscriber: Subscriber<()>,
+    tracker: Arc<PresenceTracker>,
+    _dispatch_guard: Arc<()>,
+}
+
+type DispatchEvent = Box<dyn FnOnce() + Send>;
+
+/// Per-session queue of pending callback invocations.
+/// While disabled, events run inline on the thread that produced them.
+struct DispatchQueue {
+    enabled: AtomicBool,
+    capacity: AtomicUsize,
+    events: Mutex<VecDeque<DispatchEvent>>,
+    high_water: AtomicUsize,
+    dispatched: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Routes the callbacks of one entity through its session's dispatch queue.
+/// Queued events are skipped once the entity's guard has been dropped, so a
+/// callback never runs after its subscriber or queryable was undeclared.
+#[derive(Clone)]
+struct Dispatcher {
+    queue: Arc<DispatchQueue>,
+    alive: Weak<()>,
+}
+
+/// Samples accumulated by a batched subscriber since the last flush.
+struct SampleBatch {
+    samples: Vec<Sample>,
+    first_arrival: Option<Instant>,
+}
+
+/// Batches cut by flushes but not yet delivered, oldest first.
+struct ReadyBatches {
+    batches: VecDeque<Vec<Sample>>,
+    /// True while a thread is delivering; other flushes leave their batch to it.
+    delivering: bool,
+}
+
+struct SampleBatcher {
+    batch: Mutex<SampleBatch>,
+    /// Keeps batches in arrival order while no lock is held across the callback.
+    ready: Mutex<ReadyBatches>,
+    max_samples: usize,
+    max_delay: Option<Duration>,
+    /// Wakes the batch timer when a batch opens, or when the batcher is dropped.
+    batch_opened: Arc<Notify>,
+    dispatcher: Dispatcher,
+    callback: ZenohBatchCallback,
+    context: usize,
+}
+
+struct BatchSubscriberWrapper {
+    _subscriber: Subscriber<()>,
+    batcher: Arc<SampleBatcher>,
+    _dispatch_guard: Arc<()>,
+}
+
+enum PullHandler {
+    Fifo(Subscriber<FifoChannelHandler<Sample>>),
+    Ring(Subscriber<RingChannelHandler<Sample>>),
+}
+
+struct PullSubscriberWrapper {
+    subscriber: PullHandler,
+    /// Sample that did not fit in the caller's buffers; returned by the next receive.
+    /// Locked for the whole of each receive call, see PullReceiver.
+    pending: Mutex<Option<Sample>>,
+}
+
+/// Receive side of a pull subscriber, locked for the duration of one receive call so
+/// that concurrent receivers take samples, including a kept one, in arrival order.
+struct PullReceiver<'a> {
+    subscriber: &'a PullHandler,
+    pending: MutexGuard<'a, Option<Sample>>,
+}
+
+/// Owned sample handed out by zenoh_sample_clone as a raw `Arc` pointer.
+struct SampleHandle {
+    sample: Sample,
+    /// Contiguous copy of the attachment, created on first access.
+    attachment: OnceCell<Option<Vec<u8>>>,
+}
+
+struct KeFormatWrapper {
+    /// Compiled format borrowing `spec`. Declared first so it is dropped before the
+    /// spec it points into.
+    format: KeFormat<'static>,
+    /// Heap copy of the format spec; never mutated while `format` is alive.
+    spec: Box<str>,
+}
+
+impl KeFormatWrapper {
+    /// Compiles the format once over an owned copy of the spec.
+    fn new(spec: &str) -> Result<Self, String> {
+        let spec: Box<str> = spec.into();
+        // The boxed str does not move when the Box is moved into the wrapper, and the
+        // wrapper drops `format` before `spec`, so the borrow never dangles.
+        let spec_ref: &'static str = unsafe { &*(spec.as_ref() as *const str) };
+        let format = KeFormat::new(spec_ref).map_err(|e| e.to_string())?;
+        Ok(KeFormatWrapper { format, spec })
+    }
+}
+
+struct KeyExprWrapper {
+    key_expr: KeyExpr<'static>,
+    /// The session the key expression was declared on.
+    session: Arc<Session>,
+}
+
+struct MatchingListenerWrapper {
+    _listener: MatchingListener<()>,
+    /// Holds a reference to the session to ensure it outlives the listener.
+    _session: Arc<Session>,
+    _dispatch_guard: Arc<()>,
 }
 
 // ============== QoS Types ==============
@@ -164,6 +433,64 @@ pub enum ZenohPriority {
     Background = 7,
 }
 
+/// Locality restriction for the destination of a message
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum ZenohLocality {
+    /// Local and remote entities
+    Any = 0,
+    /// Entities in the same session only
+    SessionLocal = 1,
+    /// Remote entities only
+    Remote = 2,
+}
+
+/// Kind of a time range bound
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum ZenohTimeBoundKind {
+    Unbounded = 0,
+    Inclusive = 1,
+    Exclusive = 2,
+}
+
+/// Handler backing a pull subscriber
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum ZenohChannelKind {
+    /// Ring buffer; the oldest sample is dropped when the buffer is full
+    Ring = 0,
+    /// Bounded FIFO; when the buffer is full the session's receive task waits for
+    /// space, stalling delivery to every subscriber and queryable of the session
+    Fifo = 1,
+}
+
+/// Which queryables a query should target
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum ZenohQueryTarget {
+    /// The nearest complete queryable if any, otherwise all matching queryables
+    BestMatching = 0,
+    /// All matching queryables
+    All = 1,
+    /// All complete queryables
+    AllComplete = 2,
+}
+
+/// Reply consolidation mode
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub enum ZenohConsolidationMode {
+    /// Let Zenoh choose depending on the selector
+    Auto = 0,
+    /// No consolidation: every reply from every replica is delivered
+    None = 1,
+    /// Only deliver replies newer than the last one received for the same key
+    Monotonic = 2,
+    /// Only deliver the latest reply for each key
+    Latest = 3,
+}
+
 /// Sample kind (Put or Delete)
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -218,6 +545,177 @@ pub struct ZenohAttachmentItem {
     pub value_len: usize,
 }
 
+/// Named value for key expression formats
+#[repr(C)]
+pub struct ZenohKeFormatValue {
+    /// Placeholder id, e.g. "id" for "${id:*}"
+    pub id: *const c_char,
+    pub value: *const c_char,
+}
+
+/// Selector parameter name-value pair
+#[repr(C)]
+pub struct ZenohSelectorParameter {
+    pub name: *const c_char,
+    pub value: *const c_char,
+}
+
+/// Resolved bound of a "_time" selector parameter
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ZenohTimeBound {
+    pub kind: ZenohTimeBoundKind,
+    /// NTP64 time of the bound; 0 when unbounded
+    pub time_ntp64: u64,
+}
+
+/// Options for put operations.
+/// Use zenoh_put_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PutOptions {
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string (e.g. "application/x-ros2-cdr"); overrides encoding_id when not NULL
+    pub encoding: *const c_char,
+    /// Attachment items (may be NULL if attachment_count is 0)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+    pub congestion_control: ZenohCongestionControl,
+    pub priority: ZenohPriority,
+    pub is_express: bool,
+    /// When true, `timestamp` is sent with the sample.
+    /// An all-zero timestamp id is replaced by the session's own id.
+    pub timestamp_valid: bool,
+    pub timestamp: ZenohTimestamp,
+    pub allowed_destination: ZenohLocality,
+}
+
+/// Options for publishing on a declared publisher.
+/// QoS (congestion control, priority, express, allowed destination) is fixed when
+/// the publisher is declared, so it is not part of these options.
+/// Use zenoh_publisher_put_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct PublisherPutOptions {
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string; overrides encoding_id when not NULL
+    pub encoding: *const c_char,
+    /// Attachment items (may be NULL if attachment_count is 0)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+    /// When true, `timestamp` is sent with the sample.
+    /// An all-zero timestamp id is replaced by the session's own id.
+    pub timestamp_valid: bool,
+    pub timestamp: ZenohTimestamp,
+}
+
+/// Queryable declaration options
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct QueryableOptions {
+    /// Advertise the queryable as complete for its key expression,
+    /// so it is reached by QueryTarget::AllComplete queries
+    pub complete: bool,
+    /// Restrict which queries are received (session-local, remote or any)
+    pub allowed_origin: ZenohLocality,
+}
+
+/// Options for query replies.
+/// Use zenoh_reply_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ReplyOptions {
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string; overrides encoding_id when not NULL
+    pub encoding: *const c_char,
+    /// Attachment items (may be NULL if attachment_count is 0)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+    /// When true, `timestamp` is sent with the reply.
+    /// An all-zero timestamp id is replaced by the queryable session's id.
+    pub timestamp_valid: bool,
+    pub timestamp: ZenohTimestamp,
+    pub congestion_control: ZenohCongestionControl,
+    pub priority: ZenohPriority,
+    pub is_express: bool,
+}
+
+/// Options for get operations.
+/// Use zenoh_get_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct GetOptions {
+    pub target: ZenohQueryTarget,
+    pub consolidation: ZenohConsolidationMode,
+    /// Query timeout in milliseconds (0 uses the session default)
+    pub timeout_ms: u64,
+    /// Query payload (may be NULL if payload_len is 0; NULL sends no payload)
+    pub payload: *const u8,
+    pub payload_len: usize,
+    /// Encoding of the query payload (ignored when no payload is sent)
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string; overrides encoding_id when not NULL
+    pub encoding: *const c_char,
+    /// Attachment items (may be NULL if attachment_count is 0)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+    pub allowed_destination: ZenohLocality,
+}
+
+/// Querier declaration options.
+/// Use zenoh_querier_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct QuerierOptions {
+    pub target: ZenohQueryTarget,
+    pub consolidation: ZenohConsolidationMode,
+    /// Query timeout in milliseconds (0 uses the session default)
+    pub timeout_ms: u64,
+    pub congestion_control: ZenohCongestionControl,
+    pub priority: ZenohPriority,
+    pub is_express: bool,
+    pub allowed_destination: ZenohLocality,
+}
+
+/// Options for a single querier get.
+/// Use zenoh_querier_get_options_default() to obtain a value with default settings.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct QuerierGetOptions {
+    /// Selector parameters (e.g. "a=1;b=2"); may be NULL
+    pub parameters: *const c_char,
+    /// Query payload (may be NULL if payload_len is 0; NULL sends no payload)
+    pub payload: *const u8,
+    pub payload_len: usize,
+    /// Encoding of the query payload (ignored when no payload is sent)
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string; overrides encoding_id when not NULL
+    pub encoding: *const c_char,
+    /// Attachment items (may be NULL if attachment_count is 0)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+}
+
+/// Liveliness subscriber options
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct LivelinessSubscriberOptions {
+    /// Replay the tokens that are already alive when the subscriber is declared
+    pub history: bool,
+}
+
+/// Opaque reference to a sample, valid only during the callback it was passed to.
+#[repr(C)]
+pub struct ZenohSampleRef {
+    _private: [u8; 0],
+}
+
+/// Opaque owned, reference-counted sample created by zenoh_sample_clone.
+#[repr(C)]
+pub struct ZenohOwnedSample {
+    _private: [u8; 0],
+}
+
 /// Sample data structure passed to subscriber callbacks
 #[repr(C)]
 pub struct SampleData {
@@ -225,9 +723,22 @@ pub struct SampleData {
     pub payload_data: *const u8,
     pub payload_len: usize,
     pub kind: ZenohSampleKind,
+    /// Fast-path encoding id (AppOctetStream if the encoding is not in the table)
     pub encoding_id: ZenohEncodingId,
     pub timestamp_valid: bool,
     pub timestamp: ZenohTimestamp,
+    /// Full encoding string including the schema suffix (e.g. "application/protobuf;my.Message")
+    pub encoding: *const c_char,
+    /// Raw attachment bytes (NULL if the sample has no attachment)
+    pub attachment_data: *const u8,
+    pub attachment_len: usize,
+    /// Decoded attachment items (NULL if there is no attachment or it is
+    /// not in the key_len|key|value_len|value format)
+    pub attachment_items: *const ZenohAttachmentItem,
+    pub attachment_count: usize,
+    /// Reference to the underlying sample, valid only during the callback.
+    /// Pass it to zenoh_sample_clone to retain the sample beyond the callback.
+    pub sample: *const ZenohSampleRef,
 }
 
 /// Callback function type for subscriber
@@ -236,9 +747,121 @@ pub type ZenohSubscriberCallback = unsafe extern "C" fn(*const SampleData, *mut
 /// Callback function type for queryable
 pub type ZenohQueryableCallback = unsafe extern "C" fn(*mut c_void, *mut c_void);
 
+/// Contiguous part of a payload
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ZenohPayloadSlice {
+    pub data: *const u8,
+    pub len: usize,
+}
+
+/// Allocation-free view of a sample passed to view subscriber callbacks.
+/// All pointers are valid only during the callback invocation.
+#[repr(C)]
+pub struct SampleView {
+    /// Key expression bytes (UTF-8, not NUL-terminated)
+    pub key_expr: *const u8,
+    pub key_len: usize,
+    /// Payload when it is contiguous, NULL otherwise; payload_len is always the total length
+    pub payload_data: *const u8,
+    pub payload_len: usize,
+    /// Payload as a list of slices; always set, with a single slice when contiguous
+    pub payload_slices: *const ZenohPayloadSlice,
+    pub payload_slice_count: usize,
+    pub kind: ZenohSampleKind,
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string including the schema suffix (UTF-8, not NUL-terminated)
+    pub encoding: *const u8,
+    pub encoding_len: usize,
+    pub timestamp_valid: bool,
+    pub timestamp: ZenohTimestamp,
+    /// Raw attachment bytes (NULL if the sample has no attachment)
+    pub attachment_data: *const u8,
+    pub attachment_len: usize,
+    /// Reference to the underlying sample, valid only during the callback.
+    /// Pass it to zenoh_sample_clone to retain the sample beyond the callback.
+    pub sample: *const ZenohSampleRef,
+}
+
+/// Callback function type for view subscribers
+pub type ZenohSampleViewCallback = unsafe extern "C" fn(*const SampleView, *mut c_void);
+
+/// Callback function type for batched subscribers.
+/// Receives (samples, count, context); the array and everything it points to are
+/// valid only during the callback invocation.
+pub type ZenohBatchCallback = unsafe extern "C" fn(*const SampleData, usize, *mut c_void);
+
 /// Callback function type for get (query replies)
 pub type ZenohGetCallback = unsafe extern "C" fn(*const SampleData, *mut c_void);
 
+/// Caller-provided storage for a sample received from a pull subscriber.
+/// The key and payload buffers are owned by the caller; the remaining fields are
+/// written on receive.
+#[repr(C)]
+pub struct ZenohSampleBuffer {
+    /// Buffer for the NUL-terminated key expression
+    pub key_buffer: *mut c_char,
+    pub key_capacity: usize,
+    pub payload_buffer: *mut u8,
+    pub payload_capacity: usize,
+    /// Key expression length without the terminating NUL. When BufferTooSmall is
+    /// returned, key_len and payload_len hold the sizes required.
+    pub key_len: usize,
+    pub payload_len: usize,
+    pub kind: ZenohSampleKind,
+    pub encoding_id: ZenohEncodingId,
+    pub timestamp_valid: bool,
+    pub timestamp: ZenohTimestamp,
+}
+
+/// Dispatch queue statistics of a session
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ZenohDispatchStats {
+    /// Events currently waiting to be pumped
+    pub queued: usize,
+    /// Largest queue depth observed since the queue was created
+    pub high_water: usize,
+    /// Events invoked by zenoh_session_pump
+    pub dispatched: u64,
+    /// Events discarded because the queue was full
+    pub dropped: u64,
+}
+
+/// Error reply data passed to reply error callbacks
+#[repr(C)]
+pub struct ReplyErrorData {
+    pub payload_data: *const u8,
+    pub payload_len: usize,
+    pub encoding_id: ZenohEncodingId,
+    /// Full encoding string including the schema suffix
+    pub encoding: *const c_char,
+    /// True if replier_id holds the Zenoh ID of the replying session
+    pub replier_id_valid: bool,
+    pub replier_id: [u8; 16],
+}
+
+/// Callback function type for error replies to get and querier queries.
+/// May be NULL, in which case error replies are discarded.
+pub type ZenohReplyErrorCallback = Option<unsafe extern "C" fn(*const ReplyErrorData, *mut c_void)>;
+
+/// Callback function type for asynchronous get completion.
+/// Receives (status, error_message, context); error_message is NULL on success
+/// and valid only during the callback invocation.
+pub type ZenohGetDoneCallback = unsafe extern "C" fn(ZenohError, *const c_char, *mut c_void);
+
+/// Callback function type for key enumeration.
+/// Receives (key_expr, context); key_expr is valid only during the callback invocation.
+pub type ZenohKeyCallback = unsafe extern "C" fn(*const c_char, *mut c_void);
+
+/// Callback function type for named value enumeration.
+/// Receives (id, value, context); both strings are valid only during the callback invocation.
+pub type ZenohKeyValueCallback = unsafe extern "C" fn(*const c_char, *const c_char, *mut c_void);
+
+/// Callback function type for matching listeners.
+/// Receives (matching, context) whenever the matching status changes.
+pub type ZenohMatchingCallback = unsafe extern "C" fn(bool, *mut c_void);
+
 /// Error codes
 #[repr(C)]
 pub enum ZenohError {
@@ -248,6 +871,9 @@ pub enum ZenohError {
     InvalidKeyExpr = 3,
     PutFailed = 4,
     NullPointer = 5,
+    InvalidEncoding = 6,
+    QueryFinalized = 7,
+    BufferTooSmall = 8,
     Panic = 254,
     Unknown = 255,
 }
@@ -293,8 +919,13 @@ pub extern "C" fn zenoh_open(config_json: *const c_char) -> *mut c_void {
 
         match session_result {
             Ok(session) => {
+                let dispatch = Arc::new(DispatchQueue::new());
+                let (dispatcher, dispatch_guard) = dispatch.dispatcher();
                 let handle = Box::new(SessionWrapper {
                     session: Arc::new(session),
+                    dispatch,
+                    dispatcher,
+                    _dispatch_guard: dispatch_guard,
                 });
                 Box::into_raw(handle) as *mut c_void
             }
@@ -371,6 +1002,7 @@ pub extern "C" fn zenoh_declare_publisher(
                 let pub_handle = Box::new(PublisherWrapper {
                     publisher: Arc::new(static_publisher),
                     _session: session_arc,
+                    dispatch: handle.dispatch.clone(),
                 });
                 Box::into_raw(pub_handle) as *mut c_void
             }
@@ -494,62 +1126,8 @@ pub extern "C" fn zenoh_declare_subscriber(
             }
         };
 
-        let context_ptr = context as usize;
-
-        let subscriber_result = run_blocking_local(async {
-            handle.session
-                .declare_subscriber(key)
-                .callback(move |sample: Sample| {
-                    let key_cstr = match CString::new(sample.key_expr().as_str()) {
-                        Ok(s) => s,
-                        Err(_) => return,
-                    };
-
-                    let payload = sample.payload().to_bytes();
-                    let kind = match sample.kind() {
-                        SampleKind::Put => ZenohSampleKind::Put,
-                        SampleKind::Delete => ZenohSampleKind::Delete,
-                    };
-
-                    // Get encoding
-                    let encoding_id = encoding_to_id(sample.encoding());
-
-                    // Get timestamp
-                    let (timestamp_valid, timestamp) = match sample.timestamp() {
-                        Some(ts) => {
-                            let ntp = ts.get_time().as_u64();
-                            let id_bytes = ts.get_id().to_le_bytes();
-                            let mut id = [0u8; 16];
-                            id.copy_from_slice(&id_bytes[..16.min(id_bytes.len())]);
-                            (true, ZenohTimestamp { time_ntp64: ntp, id })
-                        }
-                        None => (false, ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] }),
-                    };
-
-                    let c_sample = SampleData {
-                        key_expr: key_cstr.as_ptr(),
-                        payload_data: payload.as_ptr(),
-                        payload_len: payload.len(),
-                        kind,
-                        encoding_id,
-                        timestamp_valid,
-                        timestamp,
-                    };
-
-                    unsafe {
-                        callback(&c_sample, context_ptr as *mut c_void);
-                    }
-                })
-                .await
-        });
-
-        match subscriber_result {
-            Ok(subscriber) => {
-                let sub_handle = Box::new(SubscriberWrapper {
-                    _subscriber: Arc::new(subscriber),
-                });
-                Box::into_raw(sub_handle) as *mut c_void
-            }
+        match declare_subscriber_impl(handle, key, callback, context) {
+            Ok(sub_handle) => Box::into_raw(Box::new(sub_handle)) as *mut c_void,
             Err(e) => {
                 set_error(format!("Failed to declare subscriber: {}", e));
                 ptr::null_mut()
@@ -566,6 +1144,39 @@ pub extern "C" fn zenoh_declare_subscriber(
     }
 }
 
+/// Declares a callback subscriber on a key expression string or declared KeyExpr.
+fn declare_subscriber_impl<K>(
+    handle: &SessionWrapper,
+    key: K,
+    callback: ZenohSubscriberCallback,
+    context: *mut c_void,
+) -> zenoh::Result<SubscriberWrapper>
+where
+    K: TryInto<KeyExpr<'static>> + Send,
+    K::Error: Into<zenoh::Error>,
+{
+    let context_ptr = context as usize;
+    let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+
+    let subscriber = run_blocking_local(async {
+        handle.session
+            .declare_subscriber(key)
+            .callback(move |sample: Sample| {
+                dispatcher.dispatch(move || {
+                    with_sample_data(&sample, |c_sample| unsafe {
+                        callback(c_sample, context_ptr as *mut c_void);
+                    });
+                });
+            })
+            .await
+    })?;
+
+    Ok(SubscriberWrapper {
+        _subscriber: Arc::new(subscriber),
+        _dispatch_guard: dispatch_guard,
+    })
+}
+
 /// Undeclares and frees a subscriber.
 #[no_mangle]
 pub extern "C" fn zenoh_undeclare_subscriber(subscriber: *mut c_void) {
@@ -594,6 +1205,7 @@ pub extern "C" fn zenoh_free_string(s: *mut c_char) {
 
 /// Performs a get query (request-response pattern).
 /// Returns 0 on success, error code on failure.
+/// Error replies are discarded; use zenoh_get_with_error_callback to receive them.
 /// 
 /// # Safety
 /// The SampleData pointer passed to the callback is valid only during the callback invocation.
@@ -607,6 +1219,27 @@ pub extern "C" fn zenoh_get(
     selector: *const c_char,
     callback: ZenohGetCallback,
     context: *mut c_void,
+) -> ZenohError {
+    zenoh_get_with_error_callback(session, selector, callback, None, context)
+}
+
+/// Performs a get query, delivering error replies from queryables to `error_callback`.
+/// Returns 0 on success, error code on failure.
+/// 
+/// # Safety
+/// The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+/// during the callback invocation.
+/// Do not store these pointers or their contents for later use.
+/// Copy the data if you need to retain it.
+/// 
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_get_with_error_callback(
+    session: *mut c_void,
+    selector: *const c_char,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
 ) -> ZenohError {
     clear_error();
     
@@ -639,44 +1272,7 @@ pub extern "C" fn zenoh_get(
             match replies {
                 Ok(reply_receiver) => {
                     while let Ok(reply) = reply_receiver.recv_async().await {
-                        if let Ok(sample) = reply.result() {
-                            let key_cstr = match CString::new(sample.key_expr().as_str()) {
-                                Ok(s) => s,
-                                Err(_) => continue,
-                            };
-
-                            let payload = sample.payload().to_bytes();
-                            let kind = match sample.kind() {
-                                SampleKind::Put => ZenohSampleKind::Put,
-                                SampleKind::Delete => ZenohSampleKind::Delete,
-                            };
-
-                            let encoding_id = encoding_to_id(sample.encoding());
-                            let (timestamp_valid, timestamp) = match sample.timestamp() {
-                                Some(ts) => {
-                                    let ntp = ts.get_time().as_u64();
-                                    let id_bytes = ts.get_id().to_le_bytes();
-                                    let mut id = [0u8; 16];
-                                    id.copy_from_slice(&id_bytes[..16.min(id_bytes.len())]);
-                                    (true, ZenohTimestamp { time_ntp64: ntp, id })
-                                }
-                                None => (false, ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] }),
-                            };
-
-                            let c_sample = SampleData {
-                                key_expr: key_cstr.as_ptr(),
-                                payload_data: payload.as_ptr(),
-                                payload_len: payload.len(),
-                                kind,
-                                encoding_id,
-                                timestamp_valid,
-                                timestamp,
-                            };
-
-                            unsafe {
-                                callback(&c_sample, context_ptr as *mut c_void);
-                            }
-                        }
+                        deliver_reply(&reply, callback, error_callback, context_ptr as *mut c_void);
                     }
                     ZenohError::Ok
                 }
@@ -693,12 +1289,301 @@ pub extern "C" fn zenoh_get(
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_get");
+            set_error("Panic occurred in zenoh_get_with_error_callback");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Creates default get options
+#[no_mangle]
+pub extern "C" fn zenoh_get_options_default() -> GetOptions {
+    GetOptions {
+        target: ZenohQueryTarget::BestMatching,
+        consolidation: ZenohConsolidationMode::Auto,
+        timeout_ms: 0,
+        payload: ptr::null(),
+        payload_len: 0,
+        encoding_id: ZenohEncodingId::Empty,
+        encoding: ptr::null(),
+        attachment_items: ptr::null(),
+        attachment_count: 0,
+        allowed_destination: ZenohLocality::Any,
+    }
+}
+
+/// Owned form of GetOptions that can be moved into an async task.
+struct GetRequest {
+    target: QueryTarget,
+    consolidation: ConsolidationMode,
+    timeout: Option<Duration>,
+    payload: Option<Vec<u8>>,
+    encoding: zenoh::bytes::Encoding,
+    attachment: Option<Vec<u8>>,
+    allowed_destination: Locality,
+}
+
+impl GetRequest {
+    /// Copies the options (and the buffers they point to) into an owned request.
+    /// NULL options yield the defaults.
+    fn from_options(options: *const GetOptions) -> Result<Self, (ZenohError, String)> {
+        let opts = if options.is_null() {
+            zenoh_get_options_default()
+        } else {
+            unsafe { *options }
+        };
+
+        if opts.payload.is_null() && opts.payload_len > 0 {
+            return Err((ZenohError::NullPointer, "Payload pointer is null but length > 0".to_string()));
+        }
+        let payload = if opts.payload.is_null() {
+            None
+        } else {
+            Some(unsafe { std::slice::from_raw_parts(opts.payload, opts.payload_len) }.to_vec())
+        };
+        let encoding = resolve_encoding(opts.encoding_id, opts.encoding)
+            .map_err(|e| (ZenohError::InvalidEncoding, e))?;
+
+        Ok(GetRequest {
+            target: to_query_target(opts.target),
+            consolidation: to_consolidation(opts.consolidation),
+            timeout: (opts.timeout_ms > 0).then(|| Duration::from_millis(opts.timeout_ms)),
+            payload,
+            encoding,
+            attachment: serialize_attachment(opts.attachment_items, opts.attachment_count),
+            allowed_destination: to_locality(opts.allowed_destination),
+        })
+    }
+}
+
+/// Sends a get query and invokes `on_reply` for every reply until the reply channel closes.
+/// `selector` is either a selector string or a Selector built from a declared key expression.
+async fn run_get<'a, S>(
+    session: &'a Session,
+    selector: S,
+    request: GetRequest,
+    mut on_reply: impl FnMut(&Reply),
+) -> Result<(), String>
+where
+    S: TryInto<Selector<'a>>,
+    S::Error: Into<zenoh::Error>,
+{
+    let mut builder = session
+        .get(selector)
+        .target(request.target)
+        .consolidation(request.consolidation)
+        .allowed_destination(request.allowed_destination);
+    if let Some(timeout) = request.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(data) = request.payload {
+        builder = builder.payload(data).encoding(request.encoding);
+    }
+    if let Some(att_bytes) = request.attachment {
+        builder = builder.attachment(att_bytes);
+    }
+
+    let reply_receiver = builder
+        .await
+        .map_err(|e| format!("Get query failed: {}", e))?;
+    while let Ok(reply) = reply_receiver.recv_async().await {
+        on_reply(&reply);
+    }
+    Ok(())
+}
+
+/// Performs a get query with target, consolidation, timeout, payload,
+/// encoding, attachment and destination options.
+/// Pass NULL options for defaults.
+/// Blocks until all replies have been received or the query times out.
+/// Error replies from queryables are delivered to `error_callback`.
+/// 
+/// # Safety
+/// The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+/// during the callback invocation.
+/// Do not store these pointers or their contents for later use.
+/// Copy the data if you need to retain it.
+/// 
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_get_with_options(
+    session: *mut c_void,
+    selector: *const c_char,
+    options: *const GetOptions,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if selector.is_null() {
+            set_error("Selector is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let selector_str = unsafe {
+            match CStr::from_ptr(selector).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in selector: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let request = match GetRequest::from_options(options) {
+            Ok(request) => request,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+
+        let context_ptr = context as usize;
+
+        let query_result = run_blocking_local(run_get(&handle.session, selector_str, request, |reply| {
+            deliver_reply(reply, callback, error_callback, context_ptr as *mut c_void);
+        }));
+
+        match query_result {
+            Ok(()) => ZenohError::Ok,
+            Err(e) => {
+                set_error(e);
+                ZenohError::Unknown
+            }
+        }
+    });
+
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_get_with_options");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Performs a get query without blocking the calling thread.
+/// Returns as soon as the query has been scheduled; replies are delivered to
+/// `callback` (or `error_callback` for error replies) on a runtime thread,
+/// then `done_callback` is invoked exactly once
+/// when the reply channel closes (all replies received or timeout).
+/// Pass NULL options for defaults.
+/// 
+/// # Safety
+/// The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+/// during the callback invocation.
+/// `context` must remain valid until `done_callback` has been invoked, or until the
+/// session is closed: callbacks still queued in the dispatch queue at that point,
+/// including `done_callback`, are discarded.
+/// 
+/// Returns a non-Ok code (and never invokes the callbacks) if the arguments are invalid.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_get_async(
+    session: *mut c_void,
+    selector: *const c_char,
+    options: *const GetOptions,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    done_callback: ZenohGetDoneCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if selector.is_null() {
+            set_error("Selector is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let selector_str = unsafe {
+            match CStr::from_ptr(selector).to_str() {
+                Ok(s) => s.to_string(),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in selector: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let request = match GetRequest::from_options(options) {
+            Ok(request) => request,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+
+        let session_arc = handle.session.clone();
+        let dispatcher = handle.dispatcher.clone();
+        let context_ptr = context as usize;
+
+        RUNTIME.spawn(async move {
+            let query_result = run_get(&session_arc, selector_str.as_str(), request, |reply| {
+                let reply = reply.clone();
+                dispatcher.dispatch(move || {
+                    deliver_reply(&reply, callback, error_callback, context_ptr as *mut c_void);
+                });
+            })
+            .await;
+
+            dispatcher.dispatch(move || match query_result {
+                Ok(()) => unsafe {
+                    done_callback(ZenohError::Ok, ptr::null(), context_ptr as *mut c_void);
+                },
+                Err(e) => {
+                    let msg = CString::new(e).unwrap_or_default();
+                    unsafe {
+                        done_callback(ZenohError::Unknown, msg.as_ptr(), context_ptr as *mut c_void);
+                    }
+                }
+            });
+        });
+
+        ZenohError::Ok
+    });
+
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_get_async");
             ZenohError::Panic
         }
     }
 }
 
+/// Wraps a received query into a handle for the caller and tracks it on its queryable.
+fn register_query(
+    pending: &Mutex<Vec<Weak<QueryWrapper>>>,
+    query: Query,
+    timestamp_id: TimestampId,
+) -> *mut c_void {
+    let wrapper = Arc::new(QueryWrapper {
+        query: Mutex::new(Some(query)),
+        payload: OnceCell::new(),
+        attachment: OnceCell::new(),
+        info: OnceCell::new(),
+        timestamp_id,
+    });
+    if let Ok(mut pending) = pending.lock() {
+        pending.retain(|q| q.strong_count() > 0);
+        pending.push(Arc::downgrade(&wrapper));
+    }
+    Arc::into_raw(wrapper) as *mut c_void
+}
+
 /// Declares a queryable that responds to get queries.
 /// Returns a pointer on success, NULL on failure.
 /// Call zenoh_last_error() for error details.
@@ -708,6 +1593,30 @@ pub extern "C" fn zenoh_declare_queryable(
     key_expr: *const c_char,
     callback: ZenohQueryableCallback,
     context: *mut c_void,
+) -> *mut c_void {
+    zenoh_declare_queryable_with_options(session, key_expr, ptr::null(), callback, context)
+}
+
+/// Creates default queryable options
+#[no_mangle]
+pub extern "C" fn zenoh_queryable_options_default() -> QueryableOptions {
+    QueryableOptions {
+        complete: false,
+        allowed_origin: ZenohLocality::Any,
+    }
+}
+
+/// Declares a queryable with options (complete flag and allowed origin).
+/// Pass NULL options for defaults.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_queryable_with_options(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    options: *const QueryableOptions,
+    callback: ZenohQueryableCallback,
+    context: *mut c_void,
 ) -> *mut c_void {
     clear_error();
     
@@ -732,20 +1641,32 @@ pub extern "C" fn zenoh_declare_queryable(
             }
         };
 
+        let opts = if options.is_null() {
+            zenoh_queryable_options_default()
+        } else {
+            unsafe { *options }
+        };
+
         let context_ptr = context as usize;
+        let pending_queries: Arc<Mutex<Vec<Weak<QueryWrapper>>>> = Arc::default();
+        let callback_pending = pending_queries.clone();
+        let timestamp_id = *handle.session.new_timestamp().get_id();
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
 
         let queryable_result = run_blocking_local(async {
             handle.session
                 .declare_queryable(key)
+                .complete(opts.complete)
+                .allowed_origin(to_locality(opts.allowed_origin))
                 .callback(move |query: Query| {
-                    let query_handle = Box::new(QueryWrapper {
-                        query: Box::new(query),
-                    });
-                    let query_ptr = Box::into_raw(query_handle) as *mut c_void;
+                    let callback_pending = callback_pending.clone();
+                    dispatcher.dispatch(move || {
+                        let query_ptr = register_query(&callback_pending, query, timestamp_id);
 
-                    unsafe {
-                        callback(query_ptr, context_ptr as *mut c_void);
-                    }
+                        unsafe {
+                            callback(query_ptr, context_ptr as *mut c_void);
+                        }
+                    });
                 })
                 .await
         });
@@ -754,6 +1675,8 @@ pub extern "C" fn zenoh_declare_queryable(
             Ok(queryable) => {
                 let handle = Box::new(QueryableWrapper {
                     _queryable: Arc::new(queryable),
+                    pending_queries,
+                    _dispatch_guard: dispatch_guard,
                 });
                 Box::into_raw(handle) as *mut c_void
             }
@@ -767,14 +1690,14 @@ pub extern "C" fn zenoh_declare_queryable(
     match result {
         Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_declare_queryable");
+            set_error("Panic occurred in zenoh_declare_queryable_with_options");
             ptr::null_mut()
         }
     }
 }
 
 /// Replies to a query with data.
-/// The query handle is consumed by this operation.
+/// The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
 pub extern "C" fn zenoh_query_reply(
@@ -794,12 +1717,19 @@ pub extern "C" fn zenoh_query_reply(
             set_error("Key expression is null");
             return ZenohError::NullPointer;
         }
-        if payload.is_null() {
-            set_error("Payload pointer is null");
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
             return ZenohError::NullPointer;
         }
 
-        let query_handle = unsafe { Box::from_raw(query as *mut QueryWrapper) };
+        let query_handle = unsafe { &*(query as *const QueryWrapper) };
+        let pending_query = match query_handle.get() {
+            Ok(q) => q,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
+            }
+        };
         let key = unsafe {
             match CStr::from_ptr(key_expr).to_str() {
                 Ok(s) => s.to_string(),
@@ -809,10 +1739,14 @@ pub extern "C" fn zenoh_query_reply(
                 }
             }
         };
-        let data = unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec();
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
 
         let reply_result = run_blocking(async move {
-            query_handle.query.reply(key, data).await
+            pending_query.reply(key, data).await
         });
         match reply_result {
             Ok(_) => ZenohError::Ok,
@@ -832,248 +1766,256 @@ pub extern "C" fn zenoh_query_reply(
     }
 }
 
-/// Drops (frees) a query without replying.
-/// Use this when you receive a query but decide not to reply to it.
-/// This prevents memory leaks when queries are not replied to.
-#[no_mangle]
-pub extern "C" fn zenoh_query_drop(query: *mut c_void) {
-    if query.is_null() {
-        return;
-    }
-    let _ = panic::catch_unwind(|| {
-        unsafe {
-            let _ = Box::from_raw(query as *mut QueryWrapper);
-        }
-    });
-}
-
-/// Gets the selector (key expression) of a query.
-/// Returns a C string that must be freed with zenoh_free_string.
+/// Replies to a query with data and a full encoding string
+/// (e.g. "image/png"). Pass NULL for the default encoding.
+/// The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
+/// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_query_selector(query: *const c_void) -> *mut c_char {
+pub extern "C" fn zenoh_query_reply_with_encoding(
+    query: *mut c_void,
+    key_expr: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    encoding: *const c_char,
+) -> ZenohError {
     clear_error();
     
     let result = panic::catch_unwind(|| {
         if query.is_null() {
             set_error("Query pointer is null");
-            return ptr::null_mut();
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
         }
 
-        let handle = unsafe { &*(query as *const QueryWrapper) };
-        let selector = handle.query.selector();
-        let key_expr_str = selector.key_expr().as_str();
+        let query_handle = unsafe { &*(query as *const QueryWrapper) };
+        let pending_query = match query_handle.get() {
+            Ok(q) => q,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
+            }
+        };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s.to_string(),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+        let encoding = match resolve_encoding(ZenohEncodingId::Empty, encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
+        };
 
-        match CString::new(key_expr_str) {
-            Ok(cstr) => cstr.into_raw(),
+        let reply_result = run_blocking(async move {
+            pending_query.reply(key, data).encoding(encoding).await
+        });
+        match reply_result {
+            Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Invalid string: {}", e));
-                ptr::null_mut()
+                set_error(format!("Query reply failed: {}", e));
+                ZenohError::Unknown
             }
         }
     });
     
     match result {
-        Ok(ptr) => ptr,
+        Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_query_selector");
-            ptr::null_mut()
+            set_error("Panic occurred in zenoh_query_reply_with_encoding");
+            ZenohError::Panic
         }
     }
 }
 
-/// Undeclares and frees a queryable.
+/// Creates default reply options
 #[no_mangle]
-pub extern "C" fn zenoh_undeclare_queryable(queryable: *mut c_void) {
-    if queryable.is_null() {
-        return;
-    }
-    let _ = panic::catch_unwind(|| {
-        unsafe {
-            let _ = Box::from_raw(queryable as *mut QueryableWrapper);
-        }
-    });
-}
-
-// ============== Publisher with Options ==============
-
-/// Creates default publisher options
-#[no_mangle]
-pub extern "C" fn zenoh_publisher_options_default() -> PublisherOptions {
-    PublisherOptions {
-        congestion_control: ZenohCongestionControl::Drop,
-        priority: ZenohPriority::Data,
-        is_express: false,
+pub extern "C" fn zenoh_reply_options_default() -> ReplyOptions {
+    ReplyOptions {
+        encoding_id: ZenohEncodingId::Empty,
+        encoding: ptr::null(),
+        attachment_items: ptr::null(),
+        attachment_count: 0,
+        timestamp_valid: false,
+        timestamp: ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] },
+        congestion_control: ZenohCongestionControl::Block,
+        priority: ZenohPriority::Data,
+        is_express: false,
     }
 }
 
-/// Declares a publisher with options.
-/// Returns a pointer on success, NULL on failure.
+/// Replies to a query with data, encoding, attachment, timestamp and QoS options.
+/// Pass NULL options for defaults.
+/// The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_declare_publisher_with_options(
-    session: *mut c_void,
+pub extern "C" fn zenoh_query_reply_with_options(
+    query: *mut c_void,
     key_expr: *const c_char,
-    options: *const PublisherOptions,
-) -> *mut c_void {
+    payload: *const u8,
+    payload_len: usize,
+    options: *const ReplyOptions,
+) -> ZenohError {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
-            return ptr::null_mut();
+        if query.is_null() {
+            set_error("Query pointer is null");
+            return ZenohError::NullPointer;
         }
         if key_expr.is_null() {
             set_error("Key expression is null");
-            return ptr::null_mut();
+            return ZenohError::NullPointer;
+        }
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let query_handle = unsafe { &*(query as *const QueryWrapper) };
+        let pending_query = match query_handle.get() {
+            Ok(q) => q,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
+            }
+        };
         let key = unsafe {
             match CStr::from_ptr(key_expr).to_str() {
-                Ok(s) => s,
+                Ok(s) => s.to_string(),
                 Err(e) => {
                     set_error(format!("Invalid UTF-8 in key expression: {}", e));
-                    return ptr::null_mut();
+                    return ZenohError::InvalidKeyExpr;
                 }
             }
         };
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
 
         let opts = if options.is_null() {
-            zenoh_publisher_options_default()
+            zenoh_reply_options_default()
         } else {
             unsafe { *options }
         };
 
-        let congestion_control = match opts.congestion_control {
-            ZenohCongestionControl::Block => CongestionControl::Block,
-            ZenohCongestionControl::Drop => CongestionControl::Drop,
+        let encoding = match resolve_encoding(opts.encoding_id, opts.encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
         };
-
-        let priority = match opts.priority {
-            ZenohPriority::RealTime => Priority::RealTime,
-            ZenohPriority::InteractiveHigh => Priority::InteractiveHigh,
-            ZenohPriority::InteractiveLow => Priority::InteractiveLow,
-            ZenohPriority::DataHigh => Priority::DataHigh,
-            ZenohPriority::Data => Priority::Data,
-            ZenohPriority::DataLow => Priority::DataLow,
-            ZenohPriority::Background => Priority::Background,
+        let attachment_bytes = serialize_attachment(opts.attachment_items, opts.attachment_count);
+        let timestamp = if opts.timestamp_valid {
+            Some(to_timestamp_with_id(&opts.timestamp, query_handle.timestamp_id))
+        } else {
+            None
         };
 
-        let session_arc = handle.session.clone();
-        let publisher_result = run_blocking(async move {
-            handle.session
-                .declare_publisher(key)
-                .congestion_control(congestion_control)
-                .priority(priority)
-                .express(opts.is_express)
-                .await
-        });
-
-        match publisher_result {
-            Ok(publisher) => {
-                let static_publisher: Publisher<'static> = unsafe {
-                    std::mem::transmute(publisher)
-                };
-                let pub_handle = Box::new(PublisherWrapper {
-                    publisher: Arc::new(static_publisher),
-                    _session: session_arc,
-                });
-                Box::into_raw(pub_handle) as *mut c_void
+        let reply_result = run_blocking(async move {
+            let mut builder = pending_query
+                .reply(key, data)
+                .encoding(encoding)
+                .congestion_control(to_congestion_control(opts.congestion_control))
+                .priority(to_priority(opts.priority))
+                .express(opts.is_express);
+            if let Some(att_bytes) = attachment_bytes {
+                builder = builder.attachment(att_bytes);
+            }
+            if let Some(ts) = timestamp {
+                builder = builder.timestamp(ts);
             }
+            builder.await
+        });
+        match reply_result {
+            Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Failed to declare publisher: {}", e));
-                ptr::null_mut()
+                set_error(format!("Query reply failed: {}", e));
+                ZenohError::Unknown
             }
         }
     });
     
     match result {
-        Ok(ptr) => ptr,
+        Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_declare_publisher_with_options");
-            ptr::null_mut()
+            set_error("Panic occurred in zenoh_query_reply_with_options");
+            ZenohError::Panic
         }
     }
 }
 
-// ============== Delete Operation ==============
-
-/// Deletes data for a key expression.
-/// Returns ZenohError code.
+/// Replies to a query with an error, telling the requester the query was rejected.
+/// Pass NULL encoding for the default encoding.
+/// The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_delete(
-    session: *mut c_void,
-    key_expr: *const c_char,
+pub extern "C" fn zenoh_query_reply_err(
+    query: *mut c_void,
+    payload: *const u8,
+    payload_len: usize,
+    encoding: *const c_char,
 ) -> ZenohError {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
+        if query.is_null() {
+            set_error("Query pointer is null");
             return ZenohError::NullPointer;
         }
-        if key_expr.is_null() {
-            set_error("Key expression is null");
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
             return ZenohError::NullPointer;
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
-        let key = unsafe {
-            match CStr::from_ptr(key_expr).to_str() {
-                Ok(s) => s,
-                Err(e) => {
-                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
-                    return ZenohError::InvalidKeyExpr;
-                }
+        let query_handle = unsafe { &*(query as *const QueryWrapper) };
+        let pending_query = match query_handle.get() {
+            Ok(q) => q,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
             }
         };
-
-        let delete_result = run_blocking(async move {
-            handle.session.delete(key).await
-        });
-
-        match delete_result {
-            Ok(_) => ZenohError::Ok,
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+        let encoding = match resolve_encoding(ZenohEncodingId::Empty, encoding) {
+            Ok(enc) => enc,
             Err(e) => {
-                set_error(format!("Delete failed: {}", e));
-                ZenohError::Unknown
+                set_error(e);
+                return ZenohError::InvalidEncoding;
             }
-        }
-    });
-    
-    match result {
-        Ok(err) => err,
-        Err(_) => {
-            set_error("Panic occurred in zenoh_delete");
-            ZenohError::Panic
-        }
-    }
-}
-
-/// Deletes data using a publisher.
-/// Returns ZenohError code.
-/// Call zenoh_last_error() for error details.
-#[no_mangle]
-pub extern "C" fn zenoh_publisher_delete(publisher: *mut c_void) -> ZenohError {
-    clear_error();
-    
-    let result = panic::catch_unwind(|| {
-        if publisher.is_null() {
-            set_error("Publisher pointer is null");
-            return ZenohError::NullPointer;
-        }
-
-        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+        };
 
-        let delete_result = run_blocking(async move {
-            handle.publisher.delete().await
+        let reply_result = run_blocking(async move {
+            pending_query.reply_err(data).encoding(encoding).await
         });
-
-        match delete_result {
+        match reply_result {
             Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Publisher delete failed: {}", e));
+                set_error(format!("Query error reply failed: {}", e));
                 ZenohError::Unknown
             }
         }
@@ -1082,29 +2024,26 @@ pub extern "C" fn zenoh_publisher_delete(publisher: *mut c_void) -> ZenohError {
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_publisher_delete");
+            set_error("Panic occurred in zenoh_query_reply_err");
             ZenohError::Panic
         }
     }
 }
 
-// ============== Put with Options ==============
-
-/// Put data directly on a session (without declaring a publisher).
-/// Returns ZenohError code.
+/// Replies to a query with a delete sample for the given key expression
+/// (e.g. to report a tombstoned key).
+/// The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_put(
-    session: *mut c_void,
+pub extern "C" fn zenoh_query_reply_del(
+    query: *mut c_void,
     key_expr: *const c_char,
-    payload: *const u8,
-    payload_len: usize,
 ) -> ZenohError {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
+        if query.is_null() {
+            set_error("Query pointer is null");
             return ZenohError::NullPointer;
         }
         if key_expr.is_null() {
@@ -1112,10 +2051,17 @@ pub extern "C" fn zenoh_put(
             return ZenohError::NullPointer;
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let query_handle = unsafe { &*(query as *const QueryWrapper) };
+        let pending_query = match query_handle.get() {
+            Ok(q) => q,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
+            }
+        };
         let key = unsafe {
             match CStr::from_ptr(key_expr).to_str() {
-                Ok(s) => s,
+                Ok(s) => s.to_string(),
                 Err(e) => {
                     set_error(format!("Invalid UTF-8 in key expression: {}", e));
                     return ZenohError::InvalidKeyExpr;
@@ -1123,21 +2069,14 @@ pub extern "C" fn zenoh_put(
             }
         };
 
-        let data = if payload.is_null() || payload_len == 0 {
-            Vec::new()
-        } else {
-            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
-        };
-
-        let put_result = run_blocking(async move {
-            handle.session.put(key, data).await
+        let reply_result = run_blocking(async move {
+            pending_query.reply_del(key).await
         });
-
-        match put_result {
+        match reply_result {
             Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Put failed: {}", e));
-                ZenohError::PutFailed
+                set_error(format!("Query delete reply failed: {}", e));
+                ZenohError::Unknown
             }
         }
     });
@@ -1145,56 +2084,60 @@ pub extern "C" fn zenoh_put(
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_put");
+            set_error("Panic occurred in zenoh_query_reply_del");
             ZenohError::Panic
         }
     }
 }
 
-// ============== Liveliness ==============
+/// Finalizes a query and frees its handle.
+/// Signals the requester that no more replies will be sent. Must be called exactly
+/// once per query received by a queryable callback, whether or not it was replied to.
+/// The handle must not be used after this call.
+#[no_mangle]
+pub extern "C" fn zenoh_query_finalize(query: *mut c_void) {
+    if query.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        let query_handle = unsafe { Arc::from_raw(query as *const QueryWrapper) };
+        query_handle.finalize();
+    });
+}
 
-/// Declares a liveliness token for the given key expression.
-/// Returns a pointer on success, NULL on failure.
-/// Call zenoh_last_error() for error details.
+/// Drops (frees) a query without replying.
+/// Equivalent to zenoh_query_finalize.
 #[no_mangle]
-pub extern "C" fn zenoh_liveliness_declare_token(
-    session: *mut c_void,
-    key_expr: *const c_char,
-) -> *mut c_void {
+pub extern "C" fn zenoh_query_drop(query: *mut c_void) {
+    zenoh_query_finalize(query);
+}
+
+/// Gets the selector (key expression) of a query.
+/// Returns a C string that must be freed with zenoh_free_string.
+/// The selector stays readable if the queryable is undeclared first.
+#[no_mangle]
+pub extern "C" fn zenoh_query_selector(query: *const c_void) -> *mut c_char {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
-            return ptr::null_mut();
-        }
-        if key_expr.is_null() {
-            set_error("Key expression is null");
+        if query.is_null() {
+            set_error("Query pointer is null");
             return ptr::null_mut();
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
-        let key = unsafe {
-            match CStr::from_ptr(key_expr).to_str() {
-                Ok(s) => s,
-                Err(e) => {
-                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
-                    return ptr::null_mut();
-                }
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let info = match handle.info() {
+            Ok(info) => info,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
             }
         };
 
-        let token_result = run_blocking(async move {
-            handle.session.liveliness().declare_token(key).await
-        });
-
-        match token_result {
-            Ok(token) => {
-                let token_handle = Box::new(LivelinessTokenWrapper { _token: token });
-                Box::into_raw(token_handle) as *mut c_void
-            }
+        match CString::new(info.key_expr.as_str()) {
+            Ok(cstr) => cstr.into_raw(),
             Err(e) => {
-                set_error(format!("Failed to declare liveliness token: {}", e));
+                set_error(format!("Invalid string: {}", e));
                 ptr::null_mut()
             }
         }
@@ -1203,93 +2146,146 @@ pub extern "C" fn zenoh_liveliness_declare_token(
     match result {
         Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_liveliness_declare_token");
+            set_error("Panic occurred in zenoh_query_selector");
             ptr::null_mut()
         }
     }
 }
 
-/// Undeclares and frees a liveliness token.
+/// Gets the raw parameters of the query selector (the part after '?', e.g. "a=1;b=2").
+/// Returns a C string that must be freed with zenoh_free_string.
+/// Returns an empty string if the selector has no parameters.
+/// The parameters stay readable if the queryable is undeclared first.
 #[no_mangle]
-pub extern "C" fn zenoh_liveliness_undeclare_token(token: *mut c_void) {
-    if token.is_null() {
-        return;
-    }
-    let _ = panic::catch_unwind(|| {
-        unsafe {
-            let _ = Box::from_raw(token as *mut LivelinessTokenWrapper);
+pub extern "C" fn zenoh_query_parameters(query: *const c_void) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if query.is_null() {
+            set_error("Query pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let info = match handle.info() {
+            Ok(info) => info,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+
+        match CString::new(info.parameters.as_str()) {
+            Ok(cstr) => cstr.into_raw(),
+            Err(e) => {
+                set_error(format!("Invalid string: {}", e));
+                ptr::null_mut()
+            }
         }
     });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_query_parameters");
+            ptr::null_mut()
+        }
+    }
 }
 
-/// Callback function type for liveliness subscriber
-pub type ZenohLivelinessCallback = unsafe extern "C" fn(*const c_char, bool, *mut c_void);
-
-/// Declares a liveliness subscriber.
-/// The callback receives (key_expr, is_alive, context).
-/// Returns a pointer on success, NULL on failure.
-/// Call zenoh_last_error() for error details.
+/// Looks up a selector parameter of the query by name, decoding percent escapes as
+/// zenoh_selector_parameters does.
+/// Returns a C string that must be freed with zenoh_free_string.
+/// Returns NULL without setting an error if the parameter is not present.
 #[no_mangle]
-pub extern "C" fn zenoh_liveliness_declare_subscriber(
-    session: *mut c_void,
-    key_expr: *const c_char,
-    callback: ZenohLivelinessCallback,
-    context: *mut c_void,
-) -> *mut c_void {
+pub extern "C" fn zenoh_query_parameter(query: *const c_void, name: *const c_char) -> *mut c_char {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
+        if query.is_null() {
+            set_error("Query pointer is null");
             return ptr::null_mut();
         }
-        if key_expr.is_null() {
-            set_error("Key expression is null");
+        if name.is_null() {
+            set_error("Parameter name is null");
             return ptr::null_mut();
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
-        let key = unsafe {
-            match CStr::from_ptr(key_expr).to_str() {
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let name_str = unsafe {
+            match CStr::from_ptr(name).to_str() {
                 Ok(s) => s,
                 Err(e) => {
-                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    set_error(format!("Invalid UTF-8 in parameter name: {}", e));
                     return ptr::null_mut();
                 }
             }
         };
+        let info = match handle.info() {
+            Ok(info) => info,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
 
-        let context_ptr = context as usize;
-
-        let subscriber_result = run_blocking_local(async {
-            handle.session
-                .liveliness()
-                .declare_subscriber(key)
-                .callback(move |sample: Sample| {
-                    let key_cstr = match CString::new(sample.key_expr().as_str()) {
-                        Ok(s) => s,
-                        Err(_) => return,
-                    };
+        match info.parameters.get(escape_parameter(name_str).as_ref()) {
+            Some(value) => match CString::new(unescape_parameter(value).as_ref()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            None => ptr::null_mut(),
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_query_parameter");
+            ptr::null_mut()
+        }
+    }
+}
 
-                    let is_alive = matches!(sample.kind(), SampleKind::Put);
+/// Gets the payload sent with the query.
+/// Writes the payload length to out_len and returns a pointer to the payload bytes,
+/// valid until the handle is freed with zenoh_query_finalize. The payload stays
+/// readable if the queryable is undeclared first.
+/// Returns NULL (and writes 0) without setting an error if the query has no payload.
+#[no_mangle]
+pub extern "C" fn zenoh_query_payload(query: *const c_void, out_len: *mut usize) -> *const u8 {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if query.is_null() {
+            set_error("Query pointer is null");
+            return ptr::null();
+        }
+        if out_len.is_null() {
+            set_error("Output pointer is null");
+            return ptr::null();
+        }
 
-                    unsafe {
-                        callback(key_cstr.as_ptr(), is_alive, context_ptr as *mut c_void);
-                    }
-                })
-                .await
-        });
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let payload = match handle.payload() {
+            Ok(payload) => payload,
+            Err(e) => {
+                set_error(e);
+                return ptr::null();
+            }
+        };
 
-        match subscriber_result {
-            Ok(subscriber) => {
-                let sub_handle = Box::new(SubscriberWrapper {
-                    _subscriber: Arc::new(subscriber),
-                });
-                Box::into_raw(sub_handle) as *mut c_void
+        match payload {
+            Some(data) => {
+                unsafe { *out_len = data.len() };
+                data.as_ptr()
             }
-            Err(e) => {
-                set_error(format!("Failed to declare liveliness subscriber: {}", e));
-                ptr::null_mut()
+            None => {
+                unsafe { *out_len = 0 };
+                ptr::null()
             }
         }
     });
@@ -1297,172 +2293,242 @@ pub extern "C" fn zenoh_liveliness_declare_subscriber(
     match result {
         Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_liveliness_declare_subscriber");
-            ptr::null_mut()
+            set_error("Panic occurred in zenoh_query_payload");
+            ptr::null()
         }
     }
 }
 
-// ============== Session Info ==============
-
-/// Gets the Zenoh ID of the session as a hex string.
+/// Gets the full encoding string of the query payload (e.g. "application/json").
 /// Returns a C string that must be freed with zenoh_free_string.
-/// The format is a stable hex representation of the ZenohId bytes.
+/// Returns NULL without setting an error if the query has no payload.
+/// The encoding stays readable if the queryable is undeclared first.
 #[no_mangle]
-pub extern "C" fn zenoh_session_zid(session: *const c_void) -> *mut c_char {
+pub extern "C" fn zenoh_query_encoding(query: *const c_void) -> *mut c_char {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
+        if query.is_null() {
+            set_error("Query pointer is null");
             return ptr::null_mut();
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
-        let zid = handle.session.zid();
-        // Use stable hex representation instead of Debug format
-        let zid_bytes = zid.to_le_bytes();
-        let zid_str: String = zid_bytes.iter().map(|b| format!("{:02x}", b)).collect();
-
-        match CString::new(zid_str) {
-            Ok(cstr) => cstr.into_raw(),
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let info = match handle.info() {
+            Ok(info) => info,
             Err(e) => {
-                set_error(format!("Failed to create ZID string: {}", e));
-                ptr::null_mut()
+                set_error(e);
+                return ptr::null_mut();
             }
+        };
+
+        match info.encoding.as_deref() {
+            Some(encoding) => match CString::new(encoding) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            None => ptr::null_mut(),
         }
     });
     
     match result {
         Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_session_zid");
+            set_error("Panic occurred in zenoh_query_encoding");
             ptr::null_mut()
         }
     }
 }
 
-// ============== Encoding Helpers ==============
-
-fn encoding_to_id(encoding: &zenoh::bytes::Encoding) -> ZenohEncodingId {
-    let enc_str = encoding.to_string();
-    match enc_str.as_str() {
-        "application/octet-stream" => ZenohEncodingId::AppOctetStream,
-        "text/plain" => ZenohEncodingId::TextPlain,
-        "application/json" => ZenohEncodingId::AppJson,
-        "text/json" => ZenohEncodingId::TextJson,
-        "application/cbor" => ZenohEncodingId::AppCbor,
-        "application/yaml" => ZenohEncodingId::AppYaml,
-        "text/yaml" => ZenohEncodingId::TextYaml,
-        "text/xml" => ZenohEncodingId::TextXml,
-        "application/xml" => ZenohEncodingId::AppXml,
-        "text/csv" => ZenohEncodingId::TextCsv,
-        "application/protobuf" => ZenohEncodingId::AppProtobuf,
-        "text/html" => ZenohEncodingId::TextHtml,
-        "" => ZenohEncodingId::Empty,
-        _ => ZenohEncodingId::AppOctetStream,
-    }
-}
-
-fn id_to_encoding(id: ZenohEncodingId) -> zenoh::bytes::Encoding {
-    use zenoh::bytes::Encoding;
-    match id {
-        ZenohEncodingId::Empty => Encoding::default(),
-        ZenohEncodingId::AppOctetStream => Encoding::APPLICATION_OCTET_STREAM,
-        ZenohEncodingId::TextPlain => Encoding::TEXT_PLAIN,
-        ZenohEncodingId::AppJson => Encoding::APPLICATION_JSON,
-        ZenohEncodingId::TextJson => Encoding::TEXT_JSON,
-        ZenohEncodingId::AppCbor => Encoding::APPLICATION_CBOR,
-        ZenohEncodingId::AppYaml => Encoding::APPLICATION_YAML,
-        ZenohEncodingId::TextYaml => Encoding::TEXT_YAML,
-        ZenohEncodingId::TextXml => Encoding::TEXT_XML,
-        ZenohEncodingId::AppXml => Encoding::APPLICATION_XML,
-        ZenohEncodingId::TextCsv => Encoding::TEXT_CSV,
-        ZenohEncodingId::AppProtobuf => Encoding::APPLICATION_PROTOBUF,
-        ZenohEncodingId::TextHtml => Encoding::TEXT_HTML,
-    }
-}
-
-// ============== Put with Encoding ==============
-
-/// Publishes data with encoding on the given publisher.
-/// Call zenoh_last_error() for error details.
+/// Gets the raw attachment bytes sent with the query.
+/// Writes the length to out_len and returns a pointer valid until the handle is freed
+/// with zenoh_query_finalize. The attachment stays readable if the queryable is undeclared first.
+/// Use zenoh_attachment_parse to decode the items.
+/// Returns NULL (and writes 0) without setting an error if the query has no attachment.
 #[no_mangle]
-pub extern "C" fn zenoh_publisher_put_with_encoding(
-    publisher: *mut c_void,
-    payload: *const u8,
-    payload_len: usize,
-    encoding_id: ZenohEncodingId,
-) -> ZenohError {
+pub extern "C" fn zenoh_query_attachment(query: *const c_void, out_len: *mut usize) -> *const u8 {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if publisher.is_null() {
-            set_error("Publisher pointer is null");
-            return ZenohError::NullPointer;
+        if query.is_null() {
+            set_error("Query pointer is null");
+            return ptr::null();
         }
-
-        if payload.is_null() && payload_len > 0 {
-            set_error("Payload pointer is null but length > 0");
-            return ZenohError::NullPointer;
+        if out_len.is_null() {
+            set_error("Output pointer is null");
+            return ptr::null();
         }
 
-        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
-        let data = if payload.is_null() || payload_len == 0 {
-            Vec::new()
-        } else {
-            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let attachment = match handle.attachment() {
+            Ok(attachment) => attachment,
+            Err(e) => {
+                set_error(e);
+                return ptr::null();
+            }
         };
 
-        let encoding = id_to_encoding(encoding_id);
-
-        let put_result = run_blocking(async move {
-            handle.publisher.put(data).encoding(encoding).await
-        });
-
-        match put_result {
-            Ok(_) => ZenohError::Ok,
-            Err(e) => {
-                set_error(format!("Put with encoding failed: {}", e));
-                ZenohError::PutFailed
+        match attachment {
+            Some(data) => {
+                unsafe { *out_len = data.len() };
+                data.as_ptr()
+            }
+            None => {
+                unsafe { *out_len = 0 };
+                ptr::null()
             }
         }
     });
     
     match result {
-        Ok(err) => err,
+        Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_publisher_put_with_encoding");
-            ZenohError::Panic
+            set_error("Panic occurred in zenoh_query_attachment");
+            ptr::null()
         }
     }
 }
 
-/// Puts data directly on a key expression with encoding.
+/// Undeclares and frees a queryable.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_queryable(queryable: *mut c_void) {
+    if queryable.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(queryable as *mut QueryableWrapper);
+        }
+    });
+}
+
+// ============== Publisher with Options ==============
+
+/// Creates default publisher options
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_options_default() -> PublisherOptions {
+    PublisherOptions {
+        congestion_control: ZenohCongestionControl::Drop,
+        priority: ZenohPriority::Data,
+        is_express: false,
+    }
+}
+
+/// Declares a publisher with options.
+/// Returns a pointer on success, NULL on failure.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_put_with_encoding(
+pub extern "C" fn zenoh_declare_publisher_with_options(
     session: *mut c_void,
     key_expr: *const c_char,
-    payload: *const u8,
-    payload_len: usize,
-    encoding_id: ZenohEncodingId,
-) -> ZenohError {
+    options: *const PublisherOptions,
+) -> *mut c_void {
     clear_error();
     
     let result = panic::catch_unwind(|| {
         if session.is_null() {
             set_error("Session pointer is null");
-            return ZenohError::NullPointer;
+            return ptr::null_mut();
         }
         if key_expr.is_null() {
             set_error("Key expression is null");
-            return ZenohError::NullPointer;
+            return ptr::null_mut();
         }
 
-        if payload.is_null() && payload_len > 0 {
-            set_error("Payload pointer is null but length > 0");
-            return ZenohError::NullPointer;
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        declare_publisher_impl(handle, key, options)
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_publisher_with_options");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Declares a publisher with options on a key expression string or declared KeyExpr.
+/// Returns a publisher handle, or NULL after setting the error.
+fn declare_publisher_impl<K>(
+    handle: &SessionWrapper,
+    key: K,
+    options: *const PublisherOptions,
+) -> *mut c_void
+where
+    K: TryInto<KeyExpr<'static>> + Send,
+    K::Error: Into<zenoh::Error>,
+{
+    let opts = if options.is_null() {
+        zenoh_publisher_options_default()
+    } else {
+        unsafe { *options }
+    };
+
+    let congestion_control = to_congestion_control(opts.congestion_control);
+    let priority = to_priority(opts.priority);
+
+    let publisher_result = run_blocking_local(async {
+        handle.session
+            .declare_publisher(key)
+            .congestion_control(congestion_control)
+            .priority(priority)
+            .express(opts.is_express)
+            .await
+    });
+
+    match publisher_result {
+        Ok(publisher) => {
+            let static_publisher: Publisher<'static> = unsafe {
+                std::mem::transmute(publisher)
+            };
+            let pub_handle = Box::new(PublisherWrapper {
+                publisher: Arc::new(static_publisher),
+                _session: handle.session.clone(),
+                dispatch: handle.dispatch.clone(),
+            });
+            Box::into_raw(pub_handle) as *mut c_void
+        }
+        Err(e) => {
+            set_error(format!("Failed to declare publisher: {}", e));
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Delete Operation ==============
+
+/// Deletes data for a key expression.
+/// Returns ZenohError code.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_delete(
+    session: *mut c_void,
+    key_expr: *const c_char,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
         }
 
         let handle = unsafe { &*(session as *const SessionWrapper) };
@@ -1476,23 +2542,52 @@ pub extern "C" fn zenoh_put_with_encoding(
             }
         };
 
-        let data = if payload.is_null() || payload_len == 0 {
-            Vec::new()
-        } else {
-            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
-        };
+        let delete_result = run_blocking(async move {
+            handle.session.delete(key).await
+        });
 
-        let encoding = id_to_encoding(encoding_id);
+        match delete_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Delete failed: {}", e));
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_delete");
+            ZenohError::Panic
+        }
+    }
+}
 
-        let put_result = run_blocking(async move {
-            handle.session.put(key, data).encoding(encoding).await
+/// Deletes data using a publisher.
+/// Returns ZenohError code.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_delete(publisher: *mut c_void) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+
+        let delete_result = run_blocking(async move {
+            handle.publisher.delete().await
         });
 
-        match put_result {
+        match delete_result {
             Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Put with encoding failed: {}", e));
-                ZenohError::PutFailed
+                set_error(format!("Publisher delete failed: {}", e));
+                ZenohError::Unknown
             }
         }
     });
@@ -1500,24 +2595,23 @@ pub extern "C" fn zenoh_put_with_encoding(
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_put_with_encoding");
+            set_error("Panic occurred in zenoh_publisher_delete");
             ZenohError::Panic
         }
     }
 }
 
-// ============== Put with Attachment ==============
+// ============== Put with Options ==============
 
-/// Puts data with attachment on a key expression.
+/// Put data directly on a session (without declaring a publisher).
+/// Returns ZenohError code.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_put_with_attachment(
+pub extern "C" fn zenoh_put(
     session: *mut c_void,
     key_expr: *const c_char,
     payload: *const u8,
     payload_len: usize,
-    attachment_items: *const ZenohAttachmentItem,
-    attachment_count: usize,
 ) -> ZenohError {
     clear_error();
     
@@ -1531,11 +2625,6 @@ pub extern "C" fn zenoh_put_with_attachment(
             return ZenohError::NullPointer;
         }
 
-        if payload.is_null() && payload_len > 0 {
-            set_error("Payload pointer is null but length > 0");
-            return ZenohError::NullPointer;
-        }
-
         let handle = unsafe { &*(session as *const SessionWrapper) };
         let key = unsafe {
             match CStr::from_ptr(key_expr).to_str() {
@@ -1553,48 +2642,14 @@ pub extern "C" fn zenoh_put_with_attachment(
             unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
         };
 
-        // Build attachment as serialized bytes
-        let attachment_bytes: Option<Vec<u8>> = if !attachment_items.is_null() && attachment_count > 0 {
-            let items = unsafe { std::slice::from_raw_parts(attachment_items, attachment_count) };
-            let mut serialized = Vec::new();
-            for item in items {
-                if item.key.is_null() {
-                    continue;
-                }
-                let key_bytes = unsafe {
-                    match CStr::from_ptr(item.key).to_str() {
-                        Ok(s) => s.as_bytes(),
-                        Err(_) => continue,
-                    }
-                };
-                let value = if item.value.is_null() || item.value_len == 0 {
-                    &[]
-                } else {
-                    unsafe { std::slice::from_raw_parts(item.value, item.value_len) }
-                };
-                // Simple format: key_len(4) + key + value_len(4) + value
-                serialized.extend_from_slice(&(key_bytes.len() as u32).to_le_bytes());
-                serialized.extend_from_slice(key_bytes);
-                serialized.extend_from_slice(&(value.len() as u32).to_le_bytes());
-                serialized.extend_from_slice(value);
-            }
-            if serialized.is_empty() { None } else { Some(serialized) }
-        } else {
-            None
-        };
-
         let put_result = run_blocking(async move {
-            if let Some(att_bytes) = attachment_bytes {
-                handle.session.put(key, data).attachment(att_bytes).await
-            } else {
-                handle.session.put(key, data).await
-            }
+            handle.session.put(key, data).await
         });
 
         match put_result {
             Ok(_) => ZenohError::Ok,
             Err(e) => {
-                set_error(format!("Put with attachment failed: {}", e));
+                set_error(format!("Put failed: {}", e));
                 ZenohError::PutFailed
             }
         }
@@ -1603,62 +2658,108 @@ pub extern "C" fn zenoh_put_with_attachment(
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_put_with_attachment");
+            set_error("Panic occurred in zenoh_put");
             ZenohError::Panic
         }
     }
 }
 
-// ============== Querier ==============
+// ============== Matching ==============
 
-/// Declares a querier for repeated queries on the same key expression.
+/// Checks whether the publisher currently has matching subscribers.
+/// Writes the result to out_matching.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_declare_querier(
-    session: *mut c_void,
-    key_expr: *const c_char,
-) -> *mut c_void {
+pub extern "C" fn zenoh_publisher_has_matching_subscribers(
+    publisher: *mut c_void,
+    out_matching: *mut bool,
+) -> ZenohError {
     clear_error();
     
     let result = panic::catch_unwind(|| {
-        if session.is_null() {
-            set_error("Session pointer is null");
-            return ptr::null_mut();
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ZenohError::NullPointer;
         }
-        if key_expr.is_null() {
-            set_error("Key expression is null");
-            return ptr::null_mut();
+        if out_matching.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
         }
 
-        let handle = unsafe { &*(session as *const SessionWrapper) };
-        let key = unsafe {
-            match CStr::from_ptr(key_expr).to_str() {
-                Ok(s) => s,
-                Err(e) => {
-                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
-                    return ptr::null_mut();
-                }
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+
+        let status_result = run_blocking(async move {
+            handle.publisher.matching_status().await
+        });
+
+        match status_result {
+            Ok(status) => {
+                unsafe { *out_matching = status.matching() };
+                ZenohError::Ok
             }
-        };
+            Err(e) => {
+                set_error(format!("Failed to get matching status: {}", e));
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_publisher_has_matching_subscribers");
+            ZenohError::Panic
+        }
+    }
+}
 
-        let session_arc = handle.session.clone();
-        let querier_result = run_blocking(async move {
-            handle.session.declare_querier(key).await
+/// Declares a matching listener on a publisher.
+/// The callback receives (matching, context) each time the publisher gains its first
+/// matching subscriber or loses its last one.
+/// Returns a pointer on success, NULL on failure.
+/// Free it with zenoh_undeclare_matching_listener.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_declare_matching_listener(
+    publisher: *mut c_void,
+    callback: ZenohMatchingCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+        let context_ptr = context as usize;
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+
+        let listener_result = run_blocking_local(async {
+            handle.publisher
+                .matching_listener()
+                .callback(move |status: MatchingStatus| {
+                    let matching = status.matching();
+                    dispatcher.dispatch(move || unsafe {
+                        callback(matching, context_ptr as *mut c_void);
+                    });
+                })
+                .await
         });
 
-        match querier_result {
-            Ok(querier) => {
-                let static_querier: zenoh::query::Querier<'static> = unsafe {
-                    std::mem::transmute(querier)
-                };
-                let q_handle = Box::new(QuerierWrapper {
-                    querier: static_querier,
-                    _session: session_arc,
+        match listener_result {
+            Ok(listener) => {
+                let listener_handle = Box::new(MatchingListenerWrapper {
+                    _listener: listener,
+                    _session: handle._session.clone(),
+                    _dispatch_guard: dispatch_guard,
                 });
-                Box::into_raw(q_handle) as *mut c_void
+                Box::into_raw(listener_handle) as *mut c_void
             }
             Err(e) => {
-                set_error(format!("Failed to declare querier: {}", e));
+                set_error(format!("Failed to declare matching listener: {}", e));
                 ptr::null_mut()
             }
         }
@@ -1667,21 +2768,19 @@ pub extern "C" fn zenoh_declare_querier(
     match result {
         Ok(ptr) => ptr,
         Err(_) => {
-            set_error("Panic occurred in zenoh_declare_querier");
+            set_error("Panic occurred in zenoh_publisher_declare_matching_listener");
             ptr::null_mut()
         }
     }
 }
 
-/// Performs a get query using the querier.
-/// The callback receives SampleData pointers that are valid only during the callback invocation.
-/// Do not store these pointers for later use.
+/// Checks whether the querier currently has matching queryables.
+/// Writes the result to out_matching.
 /// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_querier_get(
+pub extern "C" fn zenoh_querier_has_matching_queryables(
     querier: *mut c_void,
-    callback: ZenohGetCallback,
-    context: *mut c_void,
+    out_matching: *mut bool,
 ) -> ZenohError {
     clear_error();
     
@@ -1690,60 +2789,24 @@ pub extern "C" fn zenoh_querier_get(
             set_error("Querier pointer is null");
             return ZenohError::NullPointer;
         }
+        if out_matching.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
 
         let handle = unsafe { &*(querier as *const QuerierWrapper) };
-        let context_ptr = context as usize;
-
-        let get_result = run_blocking_local(async {
-            handle.querier
-                .get()
-                .callback(move |reply| {
-                    if let Ok(sample) = reply.result() {
-                        let key_cstr = match CString::new(sample.key_expr().as_str()) {
-                            Ok(s) => s,
-                            Err(_) => return,
-                        };
-
-                        let payload = sample.payload().to_bytes();
-                        let kind = match sample.kind() {
-                            SampleKind::Put => ZenohSampleKind::Put,
-                            SampleKind::Delete => ZenohSampleKind::Delete,
-                        };
-
-                        let encoding_id = encoding_to_id(sample.encoding());
-                        let (timestamp_valid, timestamp) = match sample.timestamp() {
-                            Some(ts) => {
-                                let ntp = ts.get_time().as_u64();
-                                let id_bytes = ts.get_id().to_le_bytes();
-                                let mut id = [0u8; 16];
-                                id.copy_from_slice(&id_bytes[..16.min(id_bytes.len())]);
-                                (true, ZenohTimestamp { time_ntp64: ntp, id })
-                            }
-                            None => (false, ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] }),
-                        };
 
-                        let c_sample = SampleData {
-                            key_expr: key_cstr.as_ptr(),
-                            payload_data: payload.as_ptr(),
-                            payload_len: payload.len(),
-                            kind,
-                            encoding_id,
-                            timestamp_valid,
-                            timestamp,
-                        };
-
-                        unsafe {
-                            callback(&c_sample, context_ptr as *mut c_void);
-                        }
-                    }
-                })
-                .await
+        let status_result = run_blocking(async move {
+            handle.querier.matching_status().await
         });
 
-        match get_result {
-            Ok(_) => ZenohError::Ok,
+        match status_result {
+            Ok(status) => {
+                unsafe { *out_matching = status.matching() };
+                ZenohError::Ok
+            }
             Err(e) => {
-                set_error(format!("Querier get failed: {}", e));
+                set_error(format!("Failed to get matching status: {}", e));
                 ZenohError::Unknown
             }
         }
@@ -1752,70 +2815,6615 @@ pub extern "C" fn zenoh_querier_get(
     match result {
         Ok(err) => err,
         Err(_) => {
-            set_error("Panic occurred in zenoh_querier_get");
+            set_error("Panic occurred in zenoh_querier_has_matching_queryables");
             ZenohError::Panic
         }
     }
 }
 
-/// Undeclares and frees a querier.
+/// Declares a matching listener on a querier.
+/// The callback receives (matching, context) each time the querier gains its first
+/// matching queryable or loses its last one.
+/// Returns a pointer on success, NULL on failure.
+/// Free it with zenoh_undeclare_matching_listener.
+/// Call zenoh_last_error() for error details.
 #[no_mangle]
-pub extern "C" fn zenoh_undeclare_querier(querier: *mut c_void) {
-    if querier.is_null() {
+pub extern "C" fn zenoh_querier_declare_matching_listener(
+    querier: *mut c_void,
+    callback: ZenohMatchingCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if querier.is_null() {
+            set_error("Querier pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(querier as *const QuerierWrapper) };
+        let context_ptr = context as usize;
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+
+        let listener_result = run_blocking_local(async {
+            handle.querier
+                .matching_listener()
+                .callback(move |status: MatchingStatus| {
+                    let matching = status.matching();
+                    dispatcher.dispatch(move || unsafe {
+                        callback(matching, context_ptr as *mut c_void);
+                    });
+                })
+                .await
+        });
+
+        match listener_result {
+            Ok(listener) => {
+                let listener_handle = Box::new(MatchingListenerWrapper {
+                    _listener: listener,
+                    _session: handle._session.clone(),
+                    _dispatch_guard: dispatch_guard,
+                });
+                Box::into_raw(listener_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare matching listener: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_querier_declare_matching_listener");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Undeclares and frees a matching listener.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_matching_listener(listener: *mut c_void) {
+    if listener.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(listener as *mut MatchingListenerWrapper);
+        }
+    });
+}
+
+// ============== Liveliness ==============
+
+/// Declares a liveliness token for the given key expression.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_declare_token(
+    session: *mut c_void,
+    key_expr: *const c_char,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let token_result = run_blocking(async move {
+            handle.session.liveliness().declare_token(key).await
+        });
+
+        match token_result {
+            Ok(token) => {
+                let token_handle = Box::new(LivelinessTokenWrapper { _token: token });
+                Box::into_raw(token_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare liveliness token: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_liveliness_declare_token");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Undeclares and frees a liveliness token.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_undeclare_token(token: *mut c_void) {
+    if token.is_null() {
         return;
     }
-    let _ = panic::catch_unwind(|| {
-        unsafe {
-            let _ = Box::from_raw(querier as *mut QuerierWrapper);
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(token as *mut LivelinessTokenWrapper);
+        }
+    });
+}
+
+/// Callback function type for liveliness subscriber
+pub type ZenohLivelinessCallback = unsafe extern "C" fn(*const c_char, bool, *mut c_void);
+
+/// Declares a liveliness subscriber.
+/// The callback receives (key_expr, is_alive, context).
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_declare_subscriber(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    callback: ZenohLivelinessCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    zenoh_liveliness_declare_subscriber_with_options(session, key_expr, ptr::null(), callback, context)
+}
+
+/// Creates default liveliness subscriber options
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_subscriber_options_default() -> LivelinessSubscriberOptions {
+    LivelinessSubscriberOptions { history: false }
+}
+
+/// Declares a liveliness subscriber with options.
+/// With `history` enabled, the callback first receives (key_expr, true, context) for
+/// every token already alive, then live changes.
+/// Pass NULL options for defaults.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_declare_subscriber_with_options(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    options: *const LivelinessSubscriberOptions,
+    callback: ZenohLivelinessCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let opts = if options.is_null() {
+            zenoh_liveliness_subscriber_options_default()
+        } else {
+            unsafe { *options }
+        };
+
+        let context_ptr = context as usize;
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+
+        let subscriber_result = run_blocking_local(async {
+            handle.session
+                .liveliness()
+                .declare_subscriber(key)
+                .history(opts.history)
+                .callback(move |sample: Sample| {
+                    dispatcher.dispatch(move || {
+                        let key_cstr = match CString::new(sample.key_expr().as_str()) {
+                            Ok(s) => s,
+                            Err(_) => return,
+                        };
+
+                        let is_alive = matches!(sample.kind(), SampleKind::Put);
+
+                        unsafe {
+                            callback(key_cstr.as_ptr(), is_alive, context_ptr as *mut c_void);
+                        }
+                    });
+                })
+                .await
+        });
+
+        match subscriber_result {
+            Ok(subscriber) => {
+                let sub_handle = Box::new(SubscriberWrapper {
+                    _subscriber: Arc::new(subscriber),
+                    _dispatch_guard: dispatch_guard,
+                });
+                Box::into_raw(sub_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare liveliness subscriber: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_liveliness_declare_subscriber_with_options");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Queries the currently alive liveliness tokens matching `key` and invokes
+/// `on_token` for each one until the reply channel closes.
+async fn run_liveliness_get(
+    session: &Session,
+    key: &str,
+    timeout: Option<Duration>,
+    mut on_token: impl FnMut(&Sample),
+) -> Result<(), String> {
+    let mut builder = session.liveliness().get(key);
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    let reply_receiver = builder
+        .await
+        .map_err(|e| format!("Liveliness get failed: {}", e))?;
+    while let Ok(reply) = reply_receiver.recv_async().await {
+        if let Ok(sample) = reply.result() {
+            on_token(sample);
+        }
+    }
+    Ok(())
+}
+
+/// Queries the liveliness tokens currently alive on the given key expression.
+/// The callback receives (key_expr, true, context) once per alive token.
+/// Pass 0 as timeout_ms to use the session default.
+/// Blocks until all replies have been received or the query times out.
+/// The key_expr pointer is valid only during the callback invocation.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_get(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    callback: ZenohLivelinessCallback,
+    context: *mut c_void,
+    timeout_ms: u64,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        let context_ptr = context as usize;
+
+        let get_result = run_blocking_local(run_liveliness_get(&handle.session, key, timeout, |sample| {
+            if let Ok(key_cstr) = CString::new(sample.key_expr().as_str()) {
+                unsafe {
+                    callback(key_cstr.as_ptr(), true, context_ptr as *mut c_void);
+                }
+            }
+        }));
+
+        match get_result {
+            Ok(()) => ZenohError::Ok,
+            Err(e) => {
+                set_error(e);
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_liveliness_get");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Queries the liveliness tokens currently alive without blocking the calling thread.
+/// The callback receives (key_expr, true, context) once per alive token on a runtime
+/// thread, then `done_callback` is invoked exactly once when the query completes.
+/// Pass 0 as timeout_ms to use the session default.
+/// `context` must remain valid until `done_callback` has been invoked, or until the
+/// session is closed, which discards callbacks still queued in the dispatch queue.
+/// Returns a non-Ok code (and never invokes the callbacks) if the arguments are invalid.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_liveliness_get_async(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    callback: ZenohLivelinessCallback,
+    done_callback: ZenohGetDoneCallback,
+    context: *mut c_void,
+    timeout_ms: u64,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s.to_string(),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        let session_arc = handle.session.clone();
+        let dispatcher = handle.dispatcher.clone();
+        let context_ptr = context as usize;
+
+        RUNTIME.spawn(async move {
+            let get_result = run_liveliness_get(&session_arc, &key, timeout, |sample| {
+                if let Ok(key_cstr) = CString::new(sample.key_expr().as_str()) {
+                    dispatcher.dispatch(move || unsafe {
+                        callback(key_cstr.as_ptr(), true, context_ptr as *mut c_void);
+                    });
+                }
+            })
+            .await;
+
+            dispatcher.dispatch(move || match get_result {
+                Ok(()) => unsafe {
+                    done_callback(ZenohError::Ok, ptr::null(), context_ptr as *mut c_void);
+                },
+                Err(e) => {
+                    let msg = CString::new(e).unwrap_or_default();
+                    unsafe {
+                        done_callback(ZenohError::Unknown, msg.as_ptr(), context_ptr as *mut c_void);
+                    }
+                }
+            });
+        });
+
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_liveliness_get_async");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Presence Tracker ==============
+
+impl PresenceTracker {
+    fn new(dispatcher: Dispatcher, callback: ZenohLivelinessCallback, context: usize) -> Self {
+        PresenceTracker {
+            state: Mutex::new(PresenceState {
+                alive: BTreeSet::new(),
+                syncing: true,
+                removed_during_sync: HashSet::new(),
+            }),
+            delivery: Mutex::new(()),
+            dispatcher,
+            callback,
+            context,
+        }
+    }
+
+    /// Applies a join or leave from the liveliness subscriber.
+    fn on_change(&self, key: &str, is_alive: bool) {
+        let Ok(_delivering) = self.delivery.lock() else {
+            return;
+        };
+        let changed = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            if is_alive {
+                state.removed_during_sync.remove(key);
+                state.alive.insert(key.to_string())
+            } else {
+                if state.syncing {
+                    state.removed_during_sync.insert(key.to_string());
+                }
+                state.alive.remove(key)
+            }
+        };
+        if changed {
+            self.notify(key, is_alive);
+        }
+    }
+
+    /// Applies a token reported alive by the initial liveliness query, unless the
+    /// subscriber has already seen it leave.
+    fn on_initial(&self, key: &str) {
+        let Ok(_delivering) = self.delivery.lock() else {
+            return;
+        };
+        let joined = {
+            let Ok(mut state) = self.state.lock() else {
+                return;
+            };
+            !state.removed_during_sync.contains(key) && state.alive.insert(key.to_string())
+        };
+        if joined {
+            self.notify(key, true);
+        }
+    }
+
+    /// Marks the initial liveliness query as complete.
+    fn finish_sync(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.syncing = false;
+            state.removed_during_sync.clear();
+        }
+    }
+
+    fn notify(&self, key: &str, is_alive: bool) {
+        if let Ok(key_cstr) = CString::new(key) {
+            let callback = self.callback;
+            let context = self.context;
+            self.dispatcher.dispatch(move || unsafe {
+                callback(key_cstr.as_ptr(), is_alive, context as *mut c_void);
+            });
+        }
+    }
+}
+
+/// Declares a presence tracker that maintains the set of alive liveliness tokens
+/// matching the given key expression.
+/// The callback receives (key_expr, true, context) when a token joins and
+/// (key_expr, false, context) when it leaves. Tokens alive at declaration time are
+/// reported as joins before this function returns, unless the session's dispatch
+/// queue is enabled, in which case they are queued and reported by zenoh_session_pump.
+/// Blocks until the initial liveliness query completes; pass 0 as timeout_ms to use
+/// the session default.
+/// Events are delivered one at a time in the order the tracker state changed. The
+/// callback is invoked after the state lock has been released, so it may query the
+/// same tracker with zenoh_presence_snapshot, _count or _contains.
+/// 
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_presence_declare(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    callback: ZenohLivelinessCallback,
+    context: *mut c_void,
+    timeout_ms: u64,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+        let tracker = Arc::new(PresenceTracker::new(dispatcher, callback, context as usize));
+        let subscriber_tracker = tracker.clone();
+
+        // Subscribe first so that no change is missed while the initial query runs
+        let subscriber_result = run_blocking_local(async {
+            handle.session
+                .liveliness()
+                .declare_subscriber(key)
+                .callback(move |sample: Sample| {
+                    let is_alive = matches!(sample.kind(), SampleKind::Put);
+                    subscriber_tracker.on_change(sample.key_expr().as_str(), is_alive);
+                })
+                .await
+        });
+
+        let subscriber = match subscriber_result {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                set_error(format!("Failed to declare presence subscriber: {}", e));
+                return ptr::null_mut();
+            }
+        };
+
+        let timeout = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        let get_result = run_blocking_local(run_liveliness_get(&handle.session, key, timeout, |sample| {
+            tracker.on_initial(sample.key_expr().as_str());
+        }));
+        tracker.finish_sync();
+
+        if let Err(e) = get_result {
+            set_error(e);
+            return ptr::null_mut();
+        }
+
+        let tracker_handle = Box::new(PresenceTrackerWrapper {
+            _subscriber: subscriber,
+            tracker,
+            _dispatch_guard: dispatch_guard,
+        });
+        Box::into_raw(tracker_handle) as *mut c_void
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_presence_declare");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Enumerates the currently alive keys of a presence tracker in sorted order.
+/// The callback receives (key_expr, context) once per key alive when the call was made.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_presence_snapshot(
+    tracker: *const c_void,
+    callback: ZenohKeyCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if tracker.is_null() {
+            set_error("Presence tracker pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(tracker as *const PresenceTrackerWrapper) };
+        // Copy the keys so the callback runs without holding the state lock
+        let keys: Vec<CString> = match handle.tracker.state.lock() {
+            Ok(state) => state
+                .alive
+                .iter()
+                .filter_map(|key| CString::new(key.as_str()).ok())
+                .collect(),
+            Err(_) => {
+                set_error("Presence tracker state lock poisoned");
+                return ZenohError::Unknown;
+            }
+        };
+        for key_cstr in &keys {
+            unsafe {
+                callback(key_cstr.as_ptr(), context);
+            }
+        }
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_presence_snapshot");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Returns the number of currently alive keys of a presence tracker (0 on error).
+#[no_mangle]
+pub extern "C" fn zenoh_presence_count(tracker: *const c_void) -> usize {
+    if tracker.is_null() {
+        return 0;
+    }
+    panic::catch_unwind(|| {
+        let handle = unsafe { &*(tracker as *const PresenceTrackerWrapper) };
+        handle.tracker.state.lock().map(|state| state.alive.len()).unwrap_or(0)
+    })
+    .unwrap_or(0)
+}
+
+/// Returns true if the given key is currently alive in a presence tracker.
+#[no_mangle]
+pub extern "C" fn zenoh_presence_contains(tracker: *const c_void, key_expr: *const c_char) -> bool {
+    if tracker.is_null() || key_expr.is_null() {
+        return false;
+    }
+    panic::catch_unwind(|| {
+        let handle = unsafe { &*(tracker as *const PresenceTrackerWrapper) };
+        let Ok(key) = unsafe { CStr::from_ptr(key_expr) }.to_str() else {
+            return false;
+        };
+        handle.tracker.state.lock().map(|state| state.alive.contains(key)).unwrap_or(false)
+    })
+    .unwrap_or(false)
+}
+
+/// Undeclares and frees a presence tracker.
+#[no_mangle]
+pub extern "C" fn zenoh_presence_undeclare(tracker: *mut c_void) {
+    if tracker.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(tracker as *mut PresenceTrackerWrapper);
+        }
+    });
+}
+
+// ============== Key Expressions ==============
+
+/// Reads a C string and validates it as a canonical key expression.
+fn parse_keyexpr<'a>(key_expr: *const c_char) -> Result<&'a keyexpr, (ZenohError, String)> {
+    if key_expr.is_null() {
+        return Err((ZenohError::NullPointer, "Key expression is null".to_string()));
+    }
+    let key = unsafe { CStr::from_ptr(key_expr) }
+        .to_str()
+        .map_err(|e| (ZenohError::InvalidKeyExpr, format!("Invalid UTF-8 in key expression: {}", e)))?;
+    keyexpr::new(key)
+        .map_err(|e| (ZenohError::InvalidKeyExpr, format!("Invalid key expression '{}': {}", key, e)))
+}
+
+/// Validates a key expression.
+/// Returns Ok if it is a valid canonical key expression, InvalidKeyExpr otherwise;
+/// zenoh_last_error() then describes precisely what is wrong.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_validate(key_expr: *const c_char) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| match parse_keyexpr(key_expr) {
+        Ok(_) => ZenohError::Ok,
+        Err((code, msg)) => {
+            set_error(msg);
+            code
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_validate");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Canonizes a key expression (e.g. "a/**/**/b" becomes "a/**/b").
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_canonize(key_expr: *const c_char) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        match KeyExpr::autocanonize(key.to_string()) {
+            Ok(ke) => match CString::new(ke.as_str()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(format!("Invalid key expression '{}': {}", key, e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_canonize");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Checks whether two key expressions intersect (at least one key matches both).
+/// Writes the result to out_result.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_intersects(
+    left: *const c_char,
+    right: *const c_char,
+    out_result: *mut bool,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if out_result.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+        let (left, right) = match (parse_keyexpr(left), parse_keyexpr(right)) {
+            (Ok(l), Ok(r)) => (l, r),
+            (Err((code, msg)), _) | (_, Err((code, msg))) => {
+                set_error(msg);
+                return code;
+            }
+        };
+        unsafe { *out_result = left.intersects(right) };
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_intersects");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Checks whether `left` includes `right` (every key matching right also matches left).
+/// Writes the result to out_result.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_includes(
+    left: *const c_char,
+    right: *const c_char,
+    out_result: *mut bool,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if out_result.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+        let (left, right) = match (parse_keyexpr(left), parse_keyexpr(right)) {
+            (Ok(l), Ok(r)) => (l, r),
+            (Err((code, msg)), _) | (_, Err((code, msg))) => {
+                set_error(msg);
+                return code;
+            }
+        };
+        unsafe { *out_result = left.includes(right) };
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_includes");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Joins two key expressions with a '/' separator and canonizes the result.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_join(left: *const c_char, right: *const c_char) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let left = match parse_keyexpr(left) {
+            Ok(l) => l,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        if right.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+        let right = unsafe {
+            match CStr::from_ptr(right).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        match KeyExpr::from(left).join(right) {
+            Ok(ke) => match CString::new(ke.as_str()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(format!("Failed to join key expressions: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_join");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Concatenates a suffix to a key expression without a separator
+/// (e.g. "a/b" + "c" gives "a/bc"). Fails if the result would be invalid,
+/// for instance when `left` ends with a wildcard.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keyexpr_concat(left: *const c_char, right: *const c_char) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let left = match parse_keyexpr(left) {
+            Ok(l) => l,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        if right.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+        let right = unsafe {
+            match CStr::from_ptr(right).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        match KeyExpr::from(left).concat(right) {
+            Ok(ke) => match CString::new(ke.as_str()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(format!("Failed to concatenate key expressions: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keyexpr_concat");
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Key Expression Formats ==============
+
+/// Reads a C string that may not be NULL.
+fn read_cstr<'a>(s: *const c_char, what: &str) -> Result<&'a str, String> {
+    if s.is_null() {
+        return Err(format!("{} is null", what));
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|e| format!("Invalid UTF-8 in {}: {}", what, e))
+}
+
+/// Compiles a key expression format such as "game/player/${id:*}/position".
+/// Each "${name:pattern}" placeholder must be a whole chunk and is filled or
+/// extracted with zenoh_keformat_format and zenoh_keformat_parse.
+/// Returns a pointer that must be freed with zenoh_keformat_free, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keformat_new(spec: *const c_char) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let spec = match read_cstr(spec, "format spec") {
+            Ok(s) => s,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+
+        match KeFormatWrapper::new(spec) {
+            Ok(format_handle) => Box::into_raw(Box::new(format_handle)) as *mut c_void,
+            Err(e) => {
+                set_error(format!("Invalid key expression format '{}': {}", spec, e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keformat_new");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a key expression format.
+#[no_mangle]
+pub extern "C" fn zenoh_keformat_free(format: *mut c_void) {
+    if format.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(format as *mut KeFormatWrapper);
+        }
+    });
+}
+
+/// Builds a key expression from a format and named values.
+/// Every value is validated against its placeholder pattern, so a value such as
+/// "a/b" is rejected for a "${id:*}" placeholder instead of producing a wrong key.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keformat_format(
+    format: *const c_void,
+    values: *const ZenohKeFormatValue,
+    value_count: usize,
+) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if format.is_null() {
+            set_error("Key expression format pointer is null");
+            return ptr::null_mut();
+        }
+        if values.is_null() && value_count > 0 {
+            set_error("Values pointer is null but count > 0");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(format as *const KeFormatWrapper) };
+        let mut formatter = handle.format.formatter();
+
+        let values = if value_count == 0 {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(values, value_count) }
+        };
+        for item in values {
+            let (id, value) = match (read_cstr(item.id, "placeholder id"), read_cstr(item.value, "value")) {
+                (Ok(id), Ok(value)) => (id, value),
+                (Err(e), _) | (_, Err(e)) => {
+                    set_error(e);
+                    return ptr::null_mut();
+                }
+            };
+            if let Err(e) = formatter.set(id, value) {
+                set_error(format!("Invalid value '{}' for '{}': {}", value, id, e));
+                return ptr::null_mut();
+            }
+        }
+
+        match formatter.build() {
+            Ok(ke) => match CString::new(ke.as_str()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(format!("Failed to format key expression: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keformat_format");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Parses a key expression against a format and reports every placeholder.
+/// The callback receives (id, value, context) once per placeholder, in format order;
+/// value is an empty string when a "**" placeholder matched nothing.
+/// Returns InvalidKeyExpr if the key does not match the format.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keformat_parse(
+    format: *const c_void,
+    key_expr: *const c_char,
+    callback: ZenohKeyValueCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if format.is_null() {
+            set_error("Key expression format pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(format as *const KeFormatWrapper) };
+        let key = match parse_keyexpr(key_expr) {
+            Ok(k) => k,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+        let parsed = match handle.format.parse(key) {
+            Ok(p) => p,
+            Err(e) => {
+                set_error(format!("Key expression '{}' does not match format '{}': {}", key, handle.spec, e));
+                return ZenohError::InvalidKeyExpr;
+            }
+        };
+
+        for (id, value) in parsed.iter() {
+            let value = value.map(|v| v.as_str()).unwrap_or("");
+            if let (Ok(id_cstr), Ok(value_cstr)) = (CString::new(id), CString::new(value)) {
+                unsafe {
+                    callback(id_cstr.as_ptr(), value_cstr.as_ptr(), context);
+                }
+            }
+        }
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keformat_parse");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Extracts a single named value from a key expression parsed against a format.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure
+/// (including when the key does not match the format or the id is unknown).
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_keformat_parse_value(
+    format: *const c_void,
+    key_expr: *const c_char,
+    id: *const c_char,
+) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if format.is_null() {
+            set_error("Key expression format pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(format as *const KeFormatWrapper) };
+        let key = match parse_keyexpr(key_expr) {
+            Ok(k) => k,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        let id = match read_cstr(id, "placeholder id") {
+            Ok(s) => s,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+        let parsed = match handle.format.parse(key) {
+            Ok(p) => p,
+            Err(e) => {
+                set_error(format!("Key expression '{}' does not match format '{}': {}", key, handle.spec, e));
+                return ptr::null_mut();
+            }
+        };
+
+        match parsed.get(id) {
+            Ok(value) => match CString::new(value.map(|v| v.as_str()).unwrap_or("")) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            Err(e) => {
+                set_error(format!("Unknown placeholder '{}': {}", id, e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_keformat_parse_value");
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Selectors ==============
+
+/// Characters percent-escaped in selector parameter names and values: the
+/// parameter delimiters, '&' and the escape character itself.
+fn is_reserved_parameter_char(c: char) -> bool {
+    matches!(c, ';' | '=' | '&' | '%')
+}
+
+/// Percent-escapes the reserved characters of a selector parameter name or value.
+fn escape_parameter(s: &str) -> std::borrow::Cow<'_, str> {
+    if !s.contains(is_reserved_parameter_char) {
+        return std::borrow::Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 8);
+    for c in s.chars() {
+        if is_reserved_parameter_char(c) {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    std::borrow::Cow::Owned(escaped)
+}
+
+/// Decodes the percent escapes of a selector parameter name or value.
+/// Malformed escapes are kept as-is, and so is the whole input if it would not
+/// decode to valid UTF-8.
+fn unescape_parameter(s: &str) -> std::borrow::Cow<'_, str> {
+    if !s.contains('%') {
+        return std::borrow::Cow::Borrowed(s);
+    }
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = s.get(i + 1..i + 3).filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()));
+        match (bytes[i], hex) {
+            (b'%', Some(hex)) => {
+                decoded.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded)
+        .map(std::borrow::Cow::Owned)
+        .unwrap_or(std::borrow::Cow::Borrowed(s))
+}
+
+/// Parses a selector string ("key/expr?a=1;b=2").
+fn parse_selector<'a>(selector: *const c_char) -> Result<Selector<'a>, (ZenohError, String)> {
+    let selector_str = read_cstr(selector, "selector").map_err(|e| (ZenohError::NullPointer, e))?;
+    Selector::try_from(selector_str)
+        .map_err(|e| (ZenohError::InvalidKeyExpr, format!("Invalid selector '{}': {}", selector_str, e)))
+}
+
+/// Resolves the "_time" parameter against the current time and writes its bounds.
+fn write_time_range(
+    parameters: &Parameters,
+    out_present: *mut bool,
+    out_start: *mut ZenohTimeBound,
+    out_end: *mut ZenohTimeBound,
+) -> ZenohError {
+    let to_bound = |bound: TimeBound<SystemTime>| {
+        let (kind, time) = match bound {
+            TimeBound::Inclusive(t) => (ZenohTimeBoundKind::Inclusive, Some(t)),
+            TimeBound::Exclusive(t) => (ZenohTimeBoundKind::Exclusive, Some(t)),
+            TimeBound::Unbounded => (ZenohTimeBoundKind::Unbounded, None),
+        };
+        let time_ntp64 = time
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| NTP64::from(d).as_u64())
+            .unwrap_or(0);
+        ZenohTimeBound { kind, time_ntp64 }
+    };
+
+    match parameters.time_range() {
+        None => {
+            unsafe { *out_present = false };
+            ZenohError::Ok
+        }
+        Some(Ok(range)) => {
+            let range: TimeRange<SystemTime> = range.resolve_at(SystemTime::now());
+            unsafe {
+                *out_present = true;
+                *out_start = to_bound(range.start);
+                *out_end = to_bound(range.end);
+            }
+            ZenohError::Ok
+        }
+        Some(Err(e)) => {
+            set_error(format!("Invalid _time parameter: {}", e));
+            ZenohError::InvalidKeyExpr
+        }
+    }
+}
+
+/// Builds a selector string from a key expression and parameter pairs
+/// (e.g. "sensors/**" with [("_time", "[now(-1h)..]"), ("unit", "C")] gives
+/// "sensors/**?_time=[now(-1h)..];unit=C").
+/// Names must be non-empty. The characters ';', '=', '&' and '%' in names and values
+/// are percent-escaped (e.g. "a;b" becomes "a%3Bb"); zenoh_selector_parameters,
+/// zenoh_selector_parameter and zenoh_query_parameter decode them again.
+/// A "_time" value is validated as a time range.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_selector_build(
+    key_expr: *const c_char,
+    parameters: *const ZenohSelectorParameter,
+    parameter_count: usize,
+) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let key = match parse_keyexpr(key_expr) {
+            Ok(k) => k,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        if parameters.is_null() && parameter_count > 0 {
+            set_error("Parameters pointer is null but count > 0");
+            return ptr::null_mut();
+        }
+
+        let items = if parameter_count == 0 {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(parameters, parameter_count) }
+        };
+        let mut params = Parameters::empty();
+        for item in items {
+            let (name, value) = match (read_cstr(item.name, "parameter name"), read_cstr(item.value, "parameter value")) {
+                (Ok(name), Ok(value)) => (name, value),
+                (Err(e), _) | (_, Err(e)) => {
+                    set_error(e);
+                    return ptr::null_mut();
+                }
+            };
+            if name.is_empty() {
+                set_error("Parameter name is empty");
+                return ptr::null_mut();
+            }
+            params.insert(escape_parameter(name).as_ref(), escape_parameter(value).as_ref());
+        }
+        if let Some(Err(e)) = params.time_range() {
+            set_error(format!("Invalid _time parameter: {}", e));
+            return ptr::null_mut();
+        }
+
+        let selector = Selector::owned(KeyExpr::from(key), params);
+        match CString::new(selector.to_string()) {
+            Ok(cstr) => cstr.into_raw(),
+            Err(e) => {
+                set_error(format!("Invalid string: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_selector_build");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the key expression part of a selector.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_selector_key_expr(selector: *const c_char) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let selector = match parse_selector(selector) {
+            Ok(s) => s,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        match CString::new(selector.key_expr().as_str()) {
+            Ok(cstr) => cstr.into_raw(),
+            Err(e) => {
+                set_error(format!("Invalid string: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_selector_key_expr");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Enumerates the parameters of a selector, decoding percent escapes.
+/// The callback receives (name, value, context) once per parameter, in selector order.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_selector_parameters(
+    selector: *const c_char,
+    callback: ZenohKeyValueCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let selector = match parse_selector(selector) {
+            Ok(s) => s,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+        for (name, value) in selector.parameters().iter() {
+            let name = unescape_parameter(name);
+            let value = unescape_parameter(value);
+            if let (Ok(name_cstr), Ok(value_cstr)) = (CString::new(name.as_ref()), CString::new(value.as_ref())) {
+                unsafe {
+                    callback(name_cstr.as_ptr(), value_cstr.as_ptr(), context);
+                }
+            }
+        }
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_selector_parameters");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Looks up a selector parameter by name, decoding percent escapes as
+/// zenoh_selector_parameters does.
+/// Returns a C string that must be freed with zenoh_free_string.
+/// Returns NULL without setting an error if the parameter is not present.
+#[no_mangle]
+pub extern "C" fn zenoh_selector_parameter(selector: *const c_char, name: *const c_char) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let selector = match parse_selector(selector) {
+            Ok(s) => s,
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+        let name_str = match read_cstr(name, "parameter name") {
+            Ok(s) => s,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+
+        match selector.parameters().get(escape_parameter(name_str).as_ref()) {
+            Some(value) => match CString::new(unescape_parameter(value).as_ref()) {
+                Ok(cstr) => cstr.into_raw(),
+                Err(e) => {
+                    set_error(format!("Invalid string: {}", e));
+                    ptr::null_mut()
+                }
+            },
+            None => ptr::null_mut(),
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_selector_parameter");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Reads the reserved "_time" parameter of a selector, resolving relative times
+/// such as "now(-1h)" against the current time.
+/// Writes false to out_present if the selector has no "_time" parameter.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_selector_time_range(
+    selector: *const c_char,
+    out_present: *mut bool,
+    out_start: *mut ZenohTimeBound,
+    out_end: *mut ZenohTimeBound,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if out_present.is_null() || out_start.is_null() || out_end.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+        let selector = match parse_selector(selector) {
+            Ok(s) => s,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+        write_time_range(selector.parameters(), out_present, out_start, out_end)
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_selector_time_range");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Reads the reserved "_time" parameter of a query's selector.
+/// Behaves like zenoh_selector_time_range otherwise.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_query_time_range(
+    query: *const c_void,
+    out_present: *mut bool,
+    out_start: *mut ZenohTimeBound,
+    out_end: *mut ZenohTimeBound,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if query.is_null() {
+            set_error("Query pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if out_present.is_null() || out_start.is_null() || out_end.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(query as *const QueryWrapper) };
+        let info = match handle.info() {
+            Ok(info) => info,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::QueryFinalized;
+            }
+        };
+        write_time_range(&info.parameters, out_present, out_start, out_end)
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_query_time_range");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Declared Key Expressions ==============
+
+/// Borrows a declared key expression handle, checking that it belongs to `session`.
+/// A key expression id is only meaningful on the session that declared it.
+fn session_keyexpr<'a>(session: &SessionWrapper, keyexpr: *const c_void) -> Result<&'a KeyExprWrapper, String> {
+    let ke_handle = unsafe { &*(keyexpr as *const KeyExprWrapper) };
+    if !Arc::ptr_eq(&ke_handle.session, &session.session) {
+        return Err(format!(
+            "Key expression '{}' was declared on a different session",
+            ke_handle.key_expr
+        ));
+    }
+    Ok(ke_handle)
+}
+
+/// Declares a key expression on the session so that it is sent on the wire as a
+/// numeric id instead of the full string.
+/// The handle can be passed to the *_keyexpr variants of put, delete, get,
+/// subscriber and publisher declaration on the same session; those reject a handle
+/// declared on another session with InvalidKeyExpr (or NULL).
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_keyexpr(
+    session: *mut c_void,
+    key_expr: *const c_char,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = match parse_keyexpr(key_expr) {
+            Ok(k) => k.to_string(),
+            Err((_, msg)) => {
+                set_error(msg);
+                return ptr::null_mut();
+            }
+        };
+
+        let session_arc = handle.session.clone();
+        let declare_result = run_blocking(async move {
+            handle.session.declare_keyexpr(key).await
+        });
+
+        match declare_result {
+            Ok(key_expr) => {
+                let ke_handle = Box::new(KeyExprWrapper {
+                    key_expr,
+                    session: session_arc,
+                });
+                Box::into_raw(ke_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare key expression: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_keyexpr");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Undeclares and frees a declared key expression.
+/// Entities declared with it keep working; only the wire mapping is released.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_keyexpr(keyexpr: *mut c_void) {
+    if keyexpr.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        let ke_handle = unsafe { Box::from_raw(keyexpr as *mut KeyExprWrapper) };
+        let KeyExprWrapper { key_expr, session } = *ke_handle;
+        let _ = run_blocking(async move {
+            session.undeclare(key_expr).await
+        });
+    });
+}
+
+/// Puts data on a declared key expression with options.
+/// Pass NULL options for defaults.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_put_keyexpr(
+    session: *mut c_void,
+    keyexpr: *const c_void,
+    payload: *const u8,
+    payload_len: usize,
+    options: *const PutOptions,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if keyexpr.is_null() {
+            set_error("Key expression pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let ke_handle = match session_keyexpr(handle, keyexpr) {
+            Ok(ke_handle) => ke_handle,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidKeyExpr;
+            }
+        };
+
+        put_with_options_impl(&handle.session, ke_handle.key_expr.clone(), payload, payload_len, options)
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_put_keyexpr");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Deletes data for a declared key expression.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_delete_keyexpr(
+    session: *mut c_void,
+    keyexpr: *const c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if keyexpr.is_null() {
+            set_error("Key expression pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let ke_handle = match session_keyexpr(handle, keyexpr) {
+            Ok(ke_handle) => ke_handle,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidKeyExpr;
+            }
+        };
+        let key = ke_handle.key_expr.clone();
+
+        let delete_result = run_blocking(async move {
+            handle.session.delete(key).await
+        });
+
+        match delete_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Delete failed: {}", e));
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_delete_keyexpr");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Performs a get query on a declared key expression with optional selector
+/// parameters (e.g. "a=1;b=2", may be NULL) and options.
+/// Behaves like zenoh_get_with_options otherwise.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_get_keyexpr(
+    session: *mut c_void,
+    keyexpr: *const c_void,
+    parameters: *const c_char,
+    options: *const GetOptions,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if keyexpr.is_null() {
+            set_error("Key expression pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let ke_handle = match session_keyexpr(handle, keyexpr) {
+            Ok(ke_handle) => ke_handle,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidKeyExpr;
+            }
+        };
+        let params = if parameters.is_null() {
+            String::new()
+        } else {
+            match unsafe { CStr::from_ptr(parameters) }.to_str() {
+                Ok(s) => s.to_string(),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in parameters: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+        let selector = Selector::owned(ke_handle.key_expr.clone(), Parameters::from(params));
+
+        let request = match GetRequest::from_options(options) {
+            Ok(request) => request,
+            Err((code, msg)) => {
+                set_error(msg);
+                return code;
+            }
+        };
+
+        let context_ptr = context as usize;
+
+        let query_result = run_blocking_local(run_get(&handle.session, selector, request, |reply| {
+            deliver_reply(reply, callback, error_callback, context_ptr as *mut c_void);
+        }));
+
+        match query_result {
+            Ok(()) => ZenohError::Ok,
+            Err(e) => {
+                set_error(e);
+                ZenohError::Unknown
+            }
+        }
+    });
+
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_get_keyexpr");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Declares a subscriber on a declared key expression with a callback.
+/// Behaves like zenoh_declare_subscriber otherwise.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_subscriber_keyexpr(
+    session: *mut c_void,
+    keyexpr: *const c_void,
+    callback: ZenohSubscriberCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if keyexpr.is_null() {
+            set_error("Key expression pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let ke_handle = match session_keyexpr(handle, keyexpr) {
+            Ok(ke_handle) => ke_handle,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+
+        match declare_subscriber_impl(handle, ke_handle.key_expr.clone(), callback, context) {
+            Ok(sub_handle) => Box::into_raw(Box::new(sub_handle)) as *mut c_void,
+            Err(e) => {
+                set_error(format!("Failed to declare subscriber: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_subscriber_keyexpr");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Declares a publisher on a declared key expression with options.
+/// Pass NULL options for defaults.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_publisher_keyexpr(
+    session: *mut c_void,
+    keyexpr: *const c_void,
+    options: *const PublisherOptions,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if keyexpr.is_null() {
+            set_error("Key expression pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let ke_handle = match session_keyexpr(handle, keyexpr) {
+            Ok(ke_handle) => ke_handle,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+
+        declare_publisher_impl(handle, ke_handle.key_expr.clone(), options)
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_publisher_keyexpr");
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Pull Subscribers ==============
+
+/// Capacity used when zero is passed to zenoh_declare_pull_subscriber.
+const DEFAULT_PULL_CAPACITY: usize = 256;
+
+impl PullSubscriberWrapper {
+    /// Locks the receive side, waiting for any receive in progress on another thread.
+    fn receiver(&self) -> Result<PullReceiver<'_>, String> {
+        let pending = self
+            .pending
+            .lock()
+            .map_err(|_| "Pull subscriber lock poisoned".to_string())?;
+        Ok(PullReceiver { subscriber: &self.subscriber, pending })
+    }
+}
+
+impl PullReceiver<'_> {
+    fn try_recv(&mut self) -> Result<Option<Sample>, String> {
+        if let Some(sample) = self.pending.take() {
+            return Ok(Some(sample));
+        }
+        match self.subscriber {
+            PullHandler::Fifo(sub) => sub.try_recv(),
+            PullHandler::Ring(sub) => sub.try_recv(),
+        }
+        .map_err(|e| format!("Receive failed: {}", e))
+    }
+
+    fn recv_timeout(&mut self, timeout: Duration) -> Result<Option<Sample>, String> {
+        if let Some(sample) = self.pending.take() {
+            return Ok(Some(sample));
+        }
+        match self.subscriber {
+            PullHandler::Fifo(sub) => sub.recv_timeout(timeout),
+            PullHandler::Ring(sub) => sub.recv_timeout(timeout),
+        }
+        .map_err(|e| format!("Receive failed: {}", e))
+    }
+
+    /// Copies a sample into the caller's buffer, keeping it for the next receive
+    /// if it does not fit.
+    fn deliver(&mut self, sample: Sample, buffer: &mut ZenohSampleBuffer) -> ZenohError {
+        if copy_sample(&sample, buffer) {
+            return ZenohError::Ok;
+        }
+        set_error(format!(
+            "Sample buffer too small: key needs {} bytes, payload needs {} bytes",
+            buffer.key_len + 1,
+            buffer.payload_len
+        ));
+        *self.pending = Some(sample);
+        ZenohError::BufferTooSmall
+    }
+}
+
+/// Copies a sample into caller-provided buffers.
+/// Returns false with the required sizes written if a buffer is too small.
+fn copy_sample(sample: &Sample, buffer: &mut ZenohSampleBuffer) -> bool {
+    let key = sample.key_expr().as_str().as_bytes();
+    let payload = sample.payload().to_bytes();
+    buffer.key_len = key.len();
+    buffer.payload_len = payload.len();
+
+    if key.len() + 1 > buffer.key_capacity
+        || buffer.key_buffer.is_null()
+        || payload.len() > buffer.payload_capacity
+        || (buffer.payload_buffer.is_null() && !payload.is_empty())
+    {
+        return false;
+    }
+
+    unsafe {
+        ptr::copy_nonoverlapping(key.as_ptr(), buffer.key_buffer as *mut u8, key.len());
+        *buffer.key_buffer.add(key.len()) = 0;
+        if !payload.is_empty() {
+            ptr::copy_nonoverlapping(payload.as_ptr(), buffer.payload_buffer, payload.len());
+        }
+    }
+
+    buffer.kind = match sample.kind() {
+        SampleKind::Put => ZenohSampleKind::Put,
+        SampleKind::Delete => ZenohSampleKind::Delete,
+    };
+    buffer.encoding_id = encoding_to_id(sample.encoding());
+    match sample.timestamp() {
+        Some(ts) => {
+            buffer.timestamp_valid = true;
+            buffer.timestamp = to_ffi_timestamp(ts);
+        }
+        None => {
+            buffer.timestamp_valid = false;
+            buffer.timestamp = ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] };
+        }
+    }
+    true
+}
+
+/// Declares a pull subscriber whose samples are buffered natively until the
+/// application receives them with zenoh_pull_subscriber_try_recv,
+/// zenoh_pull_subscriber_recv_timeout or zenoh_pull_subscriber_recv_batch.
+/// Pass 0 as capacity for the default (256 samples).
+/// Ring (the default) never blocks the network side. Fifo loses no samples but applies
+/// backpressure to the whole session: if the application stops receiving, all
+/// traffic of the session stalls until it drains the buffer.
+/// Receive calls may be made from several threads; they are served one at a time and
+/// samples are handed out in arrival order.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_pull_subscriber(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    kind: ZenohChannelKind,
+    capacity: usize,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s.to_string(),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+        let capacity = if capacity == 0 { DEFAULT_PULL_CAPACITY } else { capacity };
+
+        let subscriber_result = run_blocking(async move {
+            match kind {
+                ZenohChannelKind::Fifo => handle.session
+                    .declare_subscriber(key)
+                    .with(FifoChannel::new(capacity))
+                    .await
+                    .map(PullHandler::Fifo),
+                ZenohChannelKind::Ring => handle.session
+                    .declare_subscriber(key)
+                    .with(RingChannel::new(capacity))
+                    .await
+                    .map(PullHandler::Ring),
+            }
+        });
+
+        match subscriber_result {
+            Ok(subscriber) => {
+                let sub_handle = Box::new(PullSubscriberWrapper {
+                    subscriber,
+                    pending: Mutex::new(None),
+                });
+                Box::into_raw(sub_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare pull subscriber: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_pull_subscriber");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Receives one sample without blocking.
+/// Writes false to out_received if no sample is available.
+/// Returns BufferTooSmall with the required sizes in the buffer if the sample does
+/// not fit; the sample is kept and returned by the next receive call.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_pull_subscriber_try_recv(
+    subscriber: *const c_void,
+    buffer: *mut ZenohSampleBuffer,
+    out_received: *mut bool,
+) -> ZenohError {
+    zenoh_pull_subscriber_recv_timeout(subscriber, buffer, 0, out_received)
+}
+
+/// Receives one sample, waiting up to timeout_ms milliseconds for one to arrive
+/// (0 does not wait).
+/// Behaves like zenoh_pull_subscriber_try_recv otherwise.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_pull_subscriber_recv_timeout(
+    subscriber: *const c_void,
+    buffer: *mut ZenohSampleBuffer,
+    timeout_ms: u64,
+    out_received: *mut bool,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if subscriber.is_null() {
+            set_error("Subscriber pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if buffer.is_null() || out_received.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(subscriber as *const PullSubscriberWrapper) };
+        let buffer = unsafe { &mut *buffer };
+        unsafe { *out_received = false };
+
+        let mut receiver = match handle.receiver() {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::Unknown;
+            }
+        };
+        let recv_result = if timeout_ms == 0 {
+            receiver.try_recv()
+        } else {
+            receiver.recv_timeout(Duration::from_millis(timeout_ms))
+        };
+
+        match recv_result {
+            Ok(Some(sample)) => {
+                let status = receiver.deliver(sample, buffer);
+                if matches!(status, ZenohError::Ok) {
+                    unsafe { *out_received = true };
+                }
+                status
+            }
+            Ok(None) => ZenohError::Ok,
+            Err(e) => {
+                set_error(e);
+                ZenohError::SessionClosed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_pull_subscriber_recv_timeout");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Receives up to buffer_count samples into consecutive buffers.
+/// Waits up to timeout_ms milliseconds for the first sample (0 does not wait), then
+/// takes whatever else is already buffered without waiting.
+/// Writes the number of filled buffers to out_received. If a sample does not fit in
+/// its buffer, the samples received so far are reported, BufferTooSmall is returned
+/// and that buffer holds the required sizes.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_pull_subscriber_recv_batch(
+    subscriber: *const c_void,
+    buffers: *mut ZenohSampleBuffer,
+    buffer_count: usize,
+    timeout_ms: u64,
+    out_received: *mut usize,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if subscriber.is_null() {
+            set_error("Subscriber pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if out_received.is_null() || (buffers.is_null() && buffer_count > 0) {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(subscriber as *const PullSubscriberWrapper) };
+        unsafe { *out_received = 0 };
+        if buffer_count == 0 {
+            return ZenohError::Ok;
+        }
+        let buffers = unsafe { std::slice::from_raw_parts_mut(buffers, buffer_count) };
+
+        let mut receiver = match handle.receiver() {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::Unknown;
+            }
+        };
+        let mut received = 0;
+        let mut status = ZenohError::Ok;
+        for buffer in buffers.iter_mut() {
+            let recv_result = if received == 0 && timeout_ms > 0 {
+                receiver.recv_timeout(Duration::from_millis(timeout_ms))
+            } else {
+                receiver.try_recv()
+            };
+            match recv_result {
+                Ok(Some(sample)) => {
+                    status = receiver.deliver(sample, buffer);
+                    if !matches!(status, ZenohError::Ok) {
+                        break;
+                    }
+                    received += 1;
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    if received == 0 {
+                        set_error(e);
+                        status = ZenohError::SessionClosed;
+                    }
+                    break;
+                }
+            }
+        }
+
+        unsafe { *out_received = received };
+        status
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_pull_subscriber_recv_batch");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Undeclares and frees a pull subscriber. Samples not yet received are discarded.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_pull_subscriber(subscriber: *mut c_void) {
+    if subscriber.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(subscriber as *mut PullSubscriberWrapper);
+        }
+    });
+}
+
+// ============== View Subscribers ==============
+
+/// Per-thread buffers reused across view deliveries so that steady-state
+/// delivery does not allocate.
+struct ViewScratch {
+    slices: Vec<ZenohPayloadSlice>,
+    encoding: String,
+    /// Copy of a fragmented attachment; contiguous attachments are passed in place.
+    attachment: Vec<u8>,
+}
+
+impl ViewScratch {
+    const fn new() -> Self {
+        ViewScratch {
+            slices: Vec::new(),
+            encoding: String::new(),
+            attachment: Vec::new(),
+        }
+    }
+}
+
+/// Builds a SampleView of the sample in `scratch` and passes it to `f`.
+fn with_sample_view<R>(sample: &Sample, scratch: &mut ViewScratch, f: impl FnOnce(&SampleView) -> R) -> R {
+    use std::fmt::Write;
+
+    let key = sample.key_expr().as_str();
+    let payload = sample.payload();
+
+    scratch.slices.clear();
+    scratch.slices.extend(payload.slices().map(|slice| ZenohPayloadSlice {
+        data: slice.as_ptr(),
+        len: slice.len(),
+    }));
+    let payload_data = match scratch.slices.as_slice() {
+        [] => [].as_ptr(),
+        [single] => single.data,
+        _ => ptr::null(),
+    };
+
+    scratch.encoding.clear();
+    let _ = write!(scratch.encoding, "{}", sample.encoding());
+
+    let (timestamp_valid, timestamp) = match sample.timestamp() {
+        Some(ts) => (true, to_ffi_timestamp(ts)),
+        None => (false, ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] }),
+    };
+
+    let (attachment_data, attachment_len) = match sample.attachment() {
+        Some(attachment) => {
+            let mut slices = attachment.slices();
+            match (slices.next(), slices.next()) {
+                (Some(single), None) => (single.as_ptr(), single.len()),
+                _ => {
+                    scratch.attachment.clear();
+                    attachment.slices().for_each(|slice| scratch.attachment.extend_from_slice(slice));
+                    (scratch.attachment.as_ptr(), scratch.attachment.len())
+                }
+            }
+        }
+        None => (ptr::null(), 0),
+    };
+
+    let view = SampleView {
+        key_expr: key.as_ptr(),
+        key_len: key.len(),
+        payload_data,
+        payload_len: payload.len(),
+        payload_slices: scratch.slices.as_ptr(),
+        payload_slice_count: scratch.slices.len(),
+        kind: match sample.kind() {
+            SampleKind::Put => ZenohSampleKind::Put,
+            SampleKind::Delete => ZenohSampleKind::Delete,
+        },
+        encoding_id: encoding_str_to_id(&scratch.encoding),
+        encoding: scratch.encoding.as_ptr(),
+        encoding_len: scratch.encoding.len(),
+        timestamp_valid,
+        timestamp,
+        attachment_data,
+        attachment_len,
+        sample: sample as *const Sample as *const ZenohSampleRef,
+    };
+    f(&view)
+}
+
+/// Passes a SampleView of the sample to the callback, building it in this thread's
+/// scratch buffers.
+fn deliver_sample_view(sample: &Sample, callback: ZenohSampleViewCallback, context: *mut c_void) {
+    let deliver = |scratch: &mut ViewScratch| {
+        with_sample_view(sample, scratch, |view| unsafe {
+            callback(view, context);
+        });
+    };
+    // A callback that pumps the dispatch queue re-enters on the same
+    // thread while the scratch buffers are in use
+    VIEW_SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+        Ok(mut scratch) => deliver(&mut scratch),
+        Err(_) => deliver(&mut ViewScratch::new()),
+    });
+}
+
+/// Declares a subscriber with an allocation-free delivery path.
+/// Instead of SampleData, the callback receives a SampleView: the key expression is
+/// passed as pointer plus length, and the payload is exposed without copying, as a
+/// single pointer when contiguous and as a slice list otherwise. The encoding string
+/// and attachment are exposed the same way. Buffers are reused per thread, so
+/// steady-state delivery performs no heap allocation.
+/// When the session's dispatch queue is enabled, each queued sample still costs one
+/// allocation for the queue entry.
+/// Returns a pointer on success, NULL on failure. Free it with zenoh_undeclare_subscriber.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_subscriber_view(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    callback: ZenohSampleViewCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let context_ptr = context as usize;
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+
+        let subscriber_result = run_blocking_local(async {
+            handle.session
+                .declare_subscriber(key)
+                .callback(move |sample: Sample| {
+                    dispatcher.dispatch(move || {
+                        deliver_sample_view(&sample, callback, context_ptr as *mut c_void);
+                    });
+                })
+                .await
+        });
+
+        match subscriber_result {
+            Ok(subscriber) => {
+                let sub_handle = Box::new(SubscriberWrapper {
+                    _subscriber: Arc::new(subscriber),
+                    _dispatch_guard: dispatch_guard,
+                });
+                Box::into_raw(sub_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare view subscriber: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_subscriber_view");
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Sample Handles ==============
+
+fn from_priority(priority: Priority) -> ZenohPriority {
+    match priority {
+        Priority::RealTime => ZenohPriority::RealTime,
+        Priority::InteractiveHigh => ZenohPriority::InteractiveHigh,
+        Priority::InteractiveLow => ZenohPriority::InteractiveLow,
+        Priority::DataHigh => ZenohPriority::DataHigh,
+        Priority::Data => ZenohPriority::Data,
+        Priority::DataLow => ZenohPriority::DataLow,
+        Priority::Background => ZenohPriority::Background,
+        // Control is reserved for zenoh's own messages
+        _ => ZenohPriority::RealTime,
+    }
+}
+
+fn from_congestion_control(cc: CongestionControl) -> ZenohCongestionControl {
+    match cc {
+        CongestionControl::Drop => ZenohCongestionControl::Drop,
+        _ => ZenohCongestionControl::Block,
+    }
+}
+
+/// Borrows the sample behind a handle, setting the error if it is NULL.
+fn sample_handle<'a>(sample: *const ZenohOwnedSample) -> Option<&'a SampleHandle> {
+    if sample.is_null() {
+        set_error("Sample pointer is null");
+        return None;
+    }
+    Some(unsafe { &*(sample as *const SampleHandle) })
+}
+
+/// Creates an owned, reference-counted handle to a sample delivered to a callback.
+/// `sample` is the `sample` field of a SampleData or SampleView and must be passed
+/// during the callback. The payload is shared with the original sample, not copied.
+/// Returns a handle that must be released with zenoh_sample_drop, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_clone(sample: *const ZenohSampleRef) -> *const ZenohOwnedSample {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if sample.is_null() {
+            set_error("Sample pointer is null");
+            return ptr::null();
+        }
+
+        let sample = unsafe { &*(sample as *const Sample) };
+        let handle = Arc::new(SampleHandle {
+            sample: sample.clone(),
+            attachment: OnceCell::new(),
+        });
+        Arc::into_raw(handle) as *const ZenohOwnedSample
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_clone");
+            ptr::null()
+        }
+    }
+}
+
+/// Adds a reference to a sample handle, e.g. before handing it to another thread.
+/// Each reference must be released with zenoh_sample_drop.
+/// Returns the same handle, or NULL if it is NULL.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_retain(sample: *const ZenohOwnedSample) -> *const ZenohOwnedSample {
+    if sample.is_null() {
+        return ptr::null();
+    }
+    unsafe { Arc::increment_strong_count(sample as *const SampleHandle) };
+    sample
+}
+
+/// Releases a reference to a sample handle; the sample is freed with the last one.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_drop(sample: *const ZenohOwnedSample) {
+    if sample.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Arc::from_raw(sample as *const SampleHandle);
+        }
+    });
+}
+
+/// Gets the key expression of a sample handle.
+/// Writes the length to out_len and returns a pointer to the UTF-8 bytes
+/// (not NUL-terminated), valid until the handle is released.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_key_expr(sample: *const ZenohOwnedSample, out_len: *mut usize) -> *const u8 {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ptr::null();
+        };
+        if out_len.is_null() {
+            set_error("Output pointer is null");
+            return ptr::null();
+        }
+
+        let key = handle.sample.key_expr().as_str();
+        unsafe { *out_len = key.len() };
+        key.as_ptr()
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_key_expr");
+            ptr::null()
+        }
+    }
+}
+
+/// Gets the total payload length of a sample handle, or 0 on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_payload_len(sample: *const ZenohOwnedSample) -> usize {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return 0;
+        };
+        handle.sample.payload().len()
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_payload_len");
+            0
+        }
+    }
+}
+
+/// Gets the number of contiguous slices the payload of a sample handle consists of,
+/// or 0 on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_payload_slice_count(sample: *const ZenohOwnedSample) -> usize {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return 0;
+        };
+        handle.sample.payload().slices().count()
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_payload_slice_count");
+            0
+        }
+    }
+}
+
+/// Gets the payload slices of a sample handle without copying, in a single pass.
+/// Writes up to `capacity` slices to out_slices, each valid until the handle is
+/// released, and returns the total number of slices; a result larger than capacity
+/// means out_slices was too small. out_slices may be NULL when capacity is 0.
+/// Returns 0 on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_payload_slices(
+    sample: *const ZenohOwnedSample,
+    out_slices: *mut ZenohPayloadSlice,
+    capacity: usize,
+) -> usize {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return 0;
+        };
+        if out_slices.is_null() && capacity > 0 {
+            set_error("Output pointer is null");
+            return 0;
+        }
+
+        let mut count = 0;
+        for (index, slice) in handle.sample.payload().slices().enumerate() {
+            if index < capacity {
+                unsafe {
+                    *out_slices.add(index) = ZenohPayloadSlice {
+                        data: slice.as_ptr(),
+                        len: slice.len(),
+                    };
+                }
+            }
+            count = index + 1;
+        }
+        count
+    });
+    
+    match result {
+        Ok(count) => count,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_payload_slices");
+            0
+        }
+    }
+}
+
+/// Gets the kind of a sample handle, or Put on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_kind(sample: *const ZenohOwnedSample) -> ZenohSampleKind {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ZenohSampleKind::Put;
+        };
+        match handle.sample.kind() {
+            SampleKind::Put => ZenohSampleKind::Put,
+            SampleKind::Delete => ZenohSampleKind::Delete,
+        }
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_kind");
+            ZenohSampleKind::Put
+        }
+    }
+}
+
+/// Gets the fast-path encoding id of a sample handle, or Empty on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_encoding_id(sample: *const ZenohOwnedSample) -> ZenohEncodingId {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ZenohEncodingId::Empty;
+        };
+        encoding_to_id(handle.sample.encoding())
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_encoding_id");
+            ZenohEncodingId::Empty
+        }
+    }
+}
+
+/// Gets the full encoding string of a sample handle, including any schema suffix.
+/// Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_encoding(sample: *const ZenohOwnedSample) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ptr::null_mut();
+        };
+        match CString::new(handle.sample.encoding().to_string()) {
+            Ok(cstr) => cstr.into_raw(),
+            Err(e) => {
+                set_error(format!("Invalid string: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_encoding");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Gets the timestamp of a sample handle.
+/// Returns true and writes it to out_timestamp if the sample has one; returns false
+/// without setting an error if it has none.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_timestamp(sample: *const ZenohOwnedSample, out_timestamp: *mut ZenohTimestamp) -> bool {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return false;
+        };
+        if out_timestamp.is_null() {
+            set_error("Output pointer is null");
+            return false;
+        }
+
+        match handle.sample.timestamp() {
+            Some(ts) => {
+                unsafe { *out_timestamp = to_ffi_timestamp(ts) };
+                true
+            }
+            None => false,
+        }
+    });
+    
+    match result {
+        Ok(valid) => valid,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_timestamp");
+            false
+        }
+    }
+}
+
+/// Gets the attachment of a sample handle.
+/// Writes the length to out_len and returns a pointer to the attachment bytes,
+/// valid until the handle is released. Use zenoh_attachment_parse to decode items.
+/// Returns NULL (and writes 0) without setting an error if the sample has no attachment.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_attachment(sample: *const ZenohOwnedSample, out_len: *mut usize) -> *const u8 {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ptr::null();
+        };
+        if out_len.is_null() {
+            set_error("Output pointer is null");
+            return ptr::null();
+        }
+
+        let attachment = handle
+            .attachment
+            .get_or_init(|| handle.sample.attachment().map(|a| a.to_bytes().into_owned()));
+        match attachment {
+            Some(data) => {
+                unsafe { *out_len = data.len() };
+                data.as_ptr()
+            }
+            None => {
+                unsafe { *out_len = 0 };
+                ptr::null()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_attachment");
+            ptr::null()
+        }
+    }
+}
+
+/// Gets the priority of a sample handle, or Data on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_priority(sample: *const ZenohOwnedSample) -> ZenohPriority {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ZenohPriority::Data;
+        };
+        from_priority(handle.sample.priority())
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_priority");
+            ZenohPriority::Data
+        }
+    }
+}
+
+/// Gets the congestion control of a sample handle, or Drop on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_congestion_control(sample: *const ZenohOwnedSample) -> ZenohCongestionControl {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return ZenohCongestionControl::Drop;
+        };
+        from_congestion_control(handle.sample.congestion_control())
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_congestion_control");
+            ZenohCongestionControl::Drop
+        }
+    }
+}
+
+/// Gets whether a sample handle was sent with express (unbatched) delivery,
+/// or false on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_sample_express(sample: *const ZenohOwnedSample) -> bool {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        let Some(handle) = sample_handle(sample) else {
+            return false;
+        };
+        handle.sample.express()
+    });
+    
+    match result {
+        Ok(value) => value,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_sample_express");
+            false
+        }
+    }
+}
+
+// ============== Batched Subscribers ==============
+
+impl SampleBatcher {
+    fn push(&self, sample: Sample) {
+        let (opened, full) = match self.batch.lock() {
+            Ok(mut batch) => {
+                let opened = batch.samples.is_empty();
+                if opened {
+                    batch.first_arrival = Some(Instant::now());
+                }
+                batch.samples.push(sample);
+                (opened, self.max_samples > 0 && batch.samples.len() >= self.max_samples)
+            }
+            Err(_) => (false, false),
+        };
+        if opened {
+            self.batch_opened.notify_one();
+        }
+        if full {
+            self.flush();
+        }
+    }
+
+    /// Delivers the accumulated samples, if any, as one batch.
+    /// If another thread is delivering, for instance when called from the callback,
+    /// the batch is queued and that thread delivers it once the current batch is done.
+    fn flush(&self) {
+        {
+            // Cut and queue under the batch lock so batches keep arrival order
+            let Ok(mut batch) = self.batch.lock() else {
+                return;
+            };
+            if batch.samples.is_empty() {
+                return;
+            }
+            let Ok(mut ready) = self.ready.lock() else {
+                return;
+            };
+            batch.first_arrival = None;
+            ready.batches.push_back(std::mem::take(&mut batch.samples));
+            if ready.delivering {
+                return;
+            }
+            ready.delivering = true;
+        }
+
+        let callback = self.callback;
+        let context_ptr = self.context;
+        loop {
+            let samples = {
+                let Ok(mut ready) = self.ready.lock() else {
+                    return;
+                };
+                match ready.batches.pop_front() {
+                    Some(samples) => samples,
+                    None => {
+                        ready.delivering = false;
+                        return;
+                    }
+                }
+            };
+            self.dispatcher.dispatch(move || {
+                with_sample_data_batch(&samples, |data| unsafe {
+                    callback(data.as_ptr(), data.len(), context_ptr as *mut c_void);
+                });
+            });
+        }
+    }
+
+    /// Time at which the current batch must be flushed, if it is time-bounded.
+    fn deadline(&self) -> Option<Instant> {
+        let first_arrival = self.batch.lock().ok()?.first_arrival?;
+        Some(first_arrival + self.max_delay?)
+    }
+}
+
+impl Drop for SampleBatcher {
+    fn drop(&mut self) {
+        // Lets the timer observe that the batcher is gone
+        self.batch_opened.notify_one();
+    }
+}
+
+/// Flushes time-bounded batches until the batcher is dropped.
+/// Sleeps until the current batch is due, and while no batch is open waits for
+/// the next one instead of polling.
+async fn run_batch_timer(batcher: Weak<SampleBatcher>, batch_opened: Arc<Notify>) {
+    loop {
+        let Some(deadline) = batcher.upgrade().map(|b| b.deadline()) else {
+            break;
+        };
+        match deadline {
+            Some(deadline) if deadline <= Instant::now() => {
+                if let Some(batcher) = batcher.upgrade() {
+                    batcher.flush();
+                }
+            }
+            Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+            // A batch opened since the check left a permit, so this returns at once
+            None => batch_opened.notified().await,
+        }
+    }
+}
+
+/// Declares a subscriber that delivers samples in batches to cut the number of
+/// callback invocations on high-rate topics.
+/// A batch is delivered as soon as it holds max_samples samples, or max_delay_us
+/// microseconds after its first sample arrived, whichever comes first. Pass 0 to
+/// disable either bound; at least one must be set. The delay is measured by the
+/// runtime timer, whose resolution is one millisecond, so a max_delay_us below 1000
+/// behaves as roughly 1 ms.
+/// The callback receives (samples, count, context) with samples in arrival order.
+/// Returns a pointer on success, NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_batch_subscriber(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    max_samples: usize,
+    max_delay_us: u64,
+    callback: ZenohBatchCallback,
+    context: *mut c_void,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+        if max_samples == 0 && max_delay_us == 0 {
+            set_error("Either max_samples or max_delay_us must be greater than 0");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let max_delay = (max_delay_us > 0).then(|| Duration::from_micros(max_delay_us));
+        let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+        let batch_opened = Arc::new(Notify::new());
+        let batcher = Arc::new(SampleBatcher {
+            batch: Mutex::new(SampleBatch {
+                samples: Vec::new(),
+                first_arrival: None,
+            }),
+            ready: Mutex::new(ReadyBatches {
+                batches: VecDeque::new(),
+                delivering: false,
+            }),
+            max_samples,
+            max_delay,
+            batch_opened: batch_opened.clone(),
+            dispatcher,
+            callback,
+            context: context as usize,
+        });
+        let callback_batcher = batcher.clone();
+
+        let subscriber_result = run_blocking_local(async {
+            handle.session
+                .declare_subscriber(key)
+                .callback(move |sample: Sample| callback_batcher.push(sample))
+                .await
+        });
+
+        match subscriber_result {
+            Ok(subscriber) => {
+                if max_delay.is_some() {
+                    RUNTIME.spawn(run_batch_timer(Arc::downgrade(&batcher), batch_opened));
+                }
+                let sub_handle = Box::new(BatchSubscriberWrapper {
+                    _subscriber: subscriber,
+                    batcher,
+                    _dispatch_guard: dispatch_guard,
+                });
+                Box::into_raw(sub_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare batch subscriber: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_batch_subscriber");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Delivers the samples accumulated by a batched subscriber immediately,
+/// without waiting for either batch bound.
+/// May be called from the batch callback: the batch is then delivered right after
+/// the callback returns, still in arrival order.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_batch_subscriber_flush(subscriber: *const c_void) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if subscriber.is_null() {
+            set_error("Subscriber pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(subscriber as *const BatchSubscriberWrapper) };
+        handle.batcher.flush();
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_batch_subscriber_flush");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Undeclares and frees a batched subscriber.
+/// Samples not yet delivered are discarded; call zenoh_batch_subscriber_flush first
+/// to receive them.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_batch_subscriber(subscriber: *mut c_void) {
+    if subscriber.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(subscriber as *mut BatchSubscriberWrapper);
+        }
+    });
+}
+
+// ============== Dispatch Queue ==============
+
+/// Queue capacity used when zero is passed to zenoh_session_enable_dispatch_queue.
+const DEFAULT_DISPATCH_CAPACITY: usize = 4096;
+
+impl DispatchQueue {
+    fn new() -> Self {
+        DispatchQueue {
+            enabled: AtomicBool::new(false),
+            capacity: AtomicUsize::new(DEFAULT_DISPATCH_CAPACITY),
+            events: Mutex::new(VecDeque::new()),
+            high_water: AtomicUsize::new(0),
+            dispatched: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Creates a dispatcher for a new entity together with the guard the entity must hold.
+    fn dispatcher(self: &Arc<Self>) -> (Dispatcher, Arc<()>) {
+        let guard = Arc::new(());
+        let dispatcher = Dispatcher {
+            queue: self.clone(),
+            alive: Arc::downgrade(&guard),
+        };
+        (dispatcher, guard)
+    }
+
+    /// Runs the event inline, or queues it for zenoh_session_pump when enabled.
+    fn dispatch(&self, event: impl FnOnce() + Send + 'static) {
+        if !self.enabled.load(Ordering::Acquire) {
+            event();
+            return;
+        }
+        let Ok(mut events) = self.events.lock() else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        if events.len() >= self.capacity.load(Ordering::Relaxed) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        events.push_back(Box::new(event));
+        self.high_water.fetch_max(events.len(), Ordering::Relaxed);
+    }
+
+    /// Invokes queued events on the calling thread until the queue is empty,
+    /// max_events have run or the budget has elapsed.
+    fn pump(&self, max_events: usize, budget: Option<Duration>) -> usize {
+        let start = Instant::now();
+        let mut count = 0;
+        while max_events == 0 || count < max_events {
+            if budget.is_some_and(|b| start.elapsed() >= b) {
+                break;
+            }
+            // Release the lock before invoking so callbacks may produce new events
+            let event = match self.events.lock() {
+                Ok(mut events) => events.pop_front(),
+                Err(_) => None,
+            };
+            let Some(event) = event else {
+                break;
+            };
+            event();
+            count += 1;
+        }
+        self.dispatched.fetch_add(count as u64, Ordering::Relaxed);
+        count
+    }
+}
+
+impl Dispatcher {
+    fn dispatch(&self, event: impl FnOnce() + Send + 'static) {
+        let alive = self.alive.clone();
+        self.queue.dispatch(move || {
+            if alive.upgrade().is_some() {
+                event();
+            }
+        });
+    }
+}
+
+/// Enables the dispatch queue of a session.
+/// While enabled, subscriber, queryable, liveliness, presence, matching listener,
+/// asynchronous get and querier callbacks are not invoked on Zenoh runtime threads but
+/// queued, and run on the thread that calls zenoh_session_pump. Callbacks of calls that block until
+/// completion (zenoh_get, zenoh_liveliness_get) already run on the calling thread.
+/// At most `capacity` events are held (0 for the default of 4096); further events are
+/// dropped and counted. Queued events of an undeclared entity are discarded, as are
+/// queued asynchronous get callbacks once the session is closed.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_session_enable_dispatch_queue(
+    session: *mut c_void,
+    capacity: usize,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let capacity = if capacity == 0 { DEFAULT_DISPATCH_CAPACITY } else { capacity };
+        handle.dispatch.capacity.store(capacity, Ordering::Relaxed);
+        handle.dispatch.enabled.store(true, Ordering::Release);
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_session_enable_dispatch_queue");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Disables the dispatch queue of a session; callbacks run inline again.
+/// Events already queued stay queued until pumped.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_session_disable_dispatch_queue(session: *mut c_void) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        handle.dispatch.enabled.store(false, Ordering::Release);
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_session_disable_dispatch_queue");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Invokes queued callbacks of a session on the calling thread, in arrival order.
+/// Stops after max_events callbacks (0 for no limit) or once budget_us microseconds
+/// have elapsed (0 for no limit), whichever comes first; a callback that is already
+/// running is never interrupted.
+/// Returns the number of callbacks invoked, or -1 on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_session_pump(
+    session: *mut c_void,
+    max_events: usize,
+    budget_us: u64,
+) -> i64 {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return -1;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let budget = (budget_us > 0).then(|| Duration::from_micros(budget_us));
+        handle.dispatch.pump(max_events, budget) as i64
+    });
+    
+    match result {
+        Ok(count) => count,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_session_pump");
+            -1
+        }
+    }
+}
+
+/// Reads the dispatch queue statistics of a session.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_session_dispatch_stats(
+    session: *mut c_void,
+    out_stats: *mut ZenohDispatchStats,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if out_stats.is_null() {
+            set_error("Output pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let queue = &handle.dispatch;
+        let queued = queue.events.lock().map(|events| events.len()).unwrap_or(0);
+        unsafe {
+            *out_stats = ZenohDispatchStats {
+                queued,
+                high_water: queue.high_water.load(Ordering::Relaxed),
+                dispatched: queue.dispatched.load(Ordering::Relaxed),
+                dropped: queue.dropped.load(Ordering::Relaxed),
+            };
+        }
+        ZenohError::Ok
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_session_dispatch_stats");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Session Info ==============
+
+/// Gets the Zenoh ID of the session as a hex string.
+/// Returns a C string that must be freed with zenoh_free_string.
+/// The format is a stable hex representation of the ZenohId bytes.
+#[no_mangle]
+pub extern "C" fn zenoh_session_zid(session: *const c_void) -> *mut c_char {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let zid = handle.session.zid();
+        // Use stable hex representation instead of Debug format
+        let zid_bytes = zid.to_le_bytes();
+        let zid_str: String = zid_bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+        match CString::new(zid_str) {
+            Ok(cstr) => cstr.into_raw(),
+            Err(e) => {
+                set_error(format!("Failed to create ZID string: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_session_zid");
+            ptr::null_mut()
+        }
+    }
+}
+
+// ============== Encoding Helpers ==============
+
+/// Maps an encoding to the fast-path id table, ignoring any schema suffix.
+fn encoding_to_id(encoding: &zenoh::bytes::Encoding) -> ZenohEncodingId {
+    encoding_str_to_id(&encoding.to_string())
+}
+
+/// Maps an encoding string to its fast-path id, ignoring any schema suffix.
+fn encoding_str_to_id(enc_str: &str) -> ZenohEncodingId {
+    let prefix = enc_str.split(';').next().unwrap_or_default();
+    match prefix {
+        "application/octet-stream" => ZenohEncodingId::AppOctetStream,
+        "text/plain" => ZenohEncodingId::TextPlain,
+        "application/json" => ZenohEncodingId::AppJson,
+        "text/json" => ZenohEncodingId::TextJson,
+        "application/cbor" => ZenohEncodingId::AppCbor,
+        "application/yaml" => ZenohEncodingId::AppYaml,
+        "text/yaml" => ZenohEncodingId::TextYaml,
+        "text/xml" => ZenohEncodingId::TextXml,
+        "application/xml" => ZenohEncodingId::AppXml,
+        "text/csv" => ZenohEncodingId::TextCsv,
+        "application/protobuf" => ZenohEncodingId::AppProtobuf,
+        "text/html" => ZenohEncodingId::TextHtml,
+        "" => ZenohEncodingId::Empty,
+        _ => ZenohEncodingId::AppOctetStream,
+    }
+}
+
+fn id_to_encoding(id: ZenohEncodingId) -> zenoh::bytes::Encoding {
+    use zenoh::bytes::Encoding;
+    match id {
+        ZenohEncodingId::Empty => Encoding::default(),
+        ZenohEncodingId::AppOctetStream => Encoding::APPLICATION_OCTET_STREAM,
+        ZenohEncodingId::TextPlain => Encoding::TEXT_PLAIN,
+        ZenohEncodingId::AppJson => Encoding::APPLICATION_JSON,
+        ZenohEncodingId::TextJson => Encoding::TEXT_JSON,
+        ZenohEncodingId::AppCbor => Encoding::APPLICATION_CBOR,
+        ZenohEncodingId::AppYaml => Encoding::APPLICATION_YAML,
+        ZenohEncodingId::TextYaml => Encoding::TEXT_YAML,
+        ZenohEncodingId::TextXml => Encoding::TEXT_XML,
+        ZenohEncodingId::AppXml => Encoding::APPLICATION_XML,
+        ZenohEncodingId::TextCsv => Encoding::TEXT_CSV,
+        ZenohEncodingId::AppProtobuf => Encoding::APPLICATION_PROTOBUF,
+        ZenohEncodingId::TextHtml => Encoding::TEXT_HTML,
+    }
+}
+
+/// Resolves the encoding of an outgoing message.
+/// A non-NULL encoding string takes precedence over the fast-path id.
+fn resolve_encoding(
+    id: ZenohEncodingId,
+    encoding: *const c_char,
+) -> Result<zenoh::bytes::Encoding, String> {
+    if encoding.is_null() {
+        return Ok(id_to_encoding(id));
+    }
+    let enc_str = unsafe { CStr::from_ptr(encoding) }
+        .to_str()
+        .map_err(|e| format!("Invalid UTF-8 in encoding: {}", e))?;
+    Ok(zenoh::bytes::Encoding::from(enc_str.to_string()))
+}
+
+// ============== QoS Helpers ==============
+
+fn to_congestion_control(cc: ZenohCongestionControl) -> CongestionControl {
+    match cc {
+        ZenohCongestionControl::Block => CongestionControl::Block,
+        ZenohCongestionControl::Drop => CongestionControl::Drop,
+    }
+}
+
+fn to_priority(priority: ZenohPriority) -> Priority {
+    match priority {
+        ZenohPriority::RealTime => Priority::RealTime,
+        ZenohPriority::InteractiveHigh => Priority::InteractiveHigh,
+        ZenohPriority::InteractiveLow => Priority::InteractiveLow,
+        ZenohPriority::DataHigh => Priority::DataHigh,
+        ZenohPriority::Data => Priority::Data,
+        ZenohPriority::DataLow => Priority::DataLow,
+        ZenohPriority::Background => Priority::Background,
+    }
+}
+
+fn to_locality(locality: ZenohLocality) -> Locality {
+    match locality {
+        ZenohLocality::Any => Locality::Any,
+        ZenohLocality::SessionLocal => Locality::SessionLocal,
+        ZenohLocality::Remote => Locality::Remote,
+    }
+}
+
+fn to_query_target(target: ZenohQueryTarget) -> QueryTarget {
+    match target {
+        ZenohQueryTarget::BestMatching => QueryTarget::BestMatching,
+        ZenohQueryTarget::All => QueryTarget::All,
+        ZenohQueryTarget::AllComplete => QueryTarget::AllComplete,
+    }
+}
+
+fn to_consolidation(mode: ZenohConsolidationMode) -> ConsolidationMode {
+    match mode {
+        ZenohConsolidationMode::Auto => ConsolidationMode::Auto,
+        ZenohConsolidationMode::None => ConsolidationMode::None,
+        ZenohConsolidationMode::Monotonic => ConsolidationMode::Monotonic,
+        ZenohConsolidationMode::Latest => ConsolidationMode::Latest,
+    }
+}
+
+/// Converts an FFI timestamp into a Zenoh timestamp.
+/// An all-zero id is replaced by the id of the given session.
+fn to_timestamp(session: &Session, ts: &ZenohTimestamp) -> Timestamp {
+    to_timestamp_with_id(ts, *session.new_timestamp().get_id())
+}
+
+/// Converts an FFI timestamp into a Zenoh timestamp.
+/// An all-zero (or otherwise invalid) id is replaced by `default_id`.
+fn to_timestamp_with_id(ts: &ZenohTimestamp, default_id: TimestampId) -> Timestamp {
+    let id = if ts.id.iter().all(|b| *b == 0) {
+        None
+    } else {
+        TimestampId::try_from(ts.id).ok()
+    };
+    Timestamp::new(NTP64(ts.time_ntp64), id.unwrap_or(default_id))
+}
+
+/// Converts a Zenoh timestamp into its FFI representation.
+fn to_ffi_timestamp(ts: &Timestamp) -> ZenohTimestamp {
+    let id_bytes = ts.get_id().to_le_bytes();
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&id_bytes[..16.min(id_bytes.len())]);
+    ZenohTimestamp { time_ntp64: ts.get_time().as_u64(), id }
+}
+
+// ============== Attachment Helpers ==============
+
+/// Serializes attachment items into the FFI attachment format.
+/// Each item is encoded as: key_len(u32 LE) + key + value_len(u32 LE) + value.
+/// Items with a NULL or non-UTF-8 key are skipped.
+/// Returns None if there is nothing to attach.
+fn serialize_attachment(items: *const ZenohAttachmentItem, count: usize) -> Option<Vec<u8>> {
+    if items.is_null() || count == 0 {
+        return None;
+    }
+    let items = unsafe { std::slice::from_raw_parts(items, count) };
+    let mut serialized = Vec::new();
+    for item in items {
+        if item.key.is_null() {
+            continue;
+        }
+        let key_bytes = unsafe {
+            match CStr::from_ptr(item.key).to_str() {
+                Ok(s) => s.as_bytes(),
+                Err(_) => continue,
+            }
+        };
+        let value = if item.value.is_null() || item.value_len == 0 {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(item.value, item.value_len) }
+        };
+        serialized.extend_from_slice(&(key_bytes.len() as u32).to_le_bytes());
+        serialized.extend_from_slice(key_bytes);
+        serialized.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        serialized.extend_from_slice(value);
+    }
+    if serialized.is_empty() { None } else { Some(serialized) }
+}
+
+/// A decoded attachment entry: NUL-terminated key and the value's byte range
+/// within the serialized attachment.
+struct AttachmentEntry {
+    key: CString,
+    value: std::ops::Range<usize>,
+}
+
+/// Decodes bytes produced by serialize_attachment().
+/// Fails if the data is truncated or a key is not valid UTF-8 without NUL bytes.
+fn decode_attachment(data: &[u8]) -> Result<Vec<AttachmentEntry>, String> {
+    fn read_len(data: &[u8], pos: &mut usize) -> Result<usize, String> {
+        let bytes = data
+            .get(*pos..*pos + 4)
+            .ok_or_else(|| format!("Truncated attachment length at offset {}", pos))?;
+        *pos += 4;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    }
+
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let key_len = read_len(data, &mut pos)?;
+        let key_bytes = data
+            .get(pos..pos + key_len)
+            .ok_or_else(|| format!("Truncated attachment key at offset {}", pos))?;
+        let key_str = std::str::from_utf8(key_bytes)
+            .map_err(|e| format!("Invalid UTF-8 in attachment key: {}", e))?;
+        let key = CString::new(key_str)
+            .map_err(|e| format!("Invalid attachment key: {}", e))?;
+        pos += key_len;
+
+        let value_len = read_len(data, &mut pos)?;
+        if data.len() - pos < value_len {
+            return Err(format!("Truncated attachment value at offset {}", pos));
+        }
+        entries.push(AttachmentEntry { key, value: pos..pos + value_len });
+        pos += value_len;
+    }
+    Ok(entries)
+}
+
+/// Builds FFI items for decoded entries. The items borrow from `data` and `entries`.
+fn attachment_items(data: &[u8], entries: &[AttachmentEntry]) -> Vec<ZenohAttachmentItem> {
+    entries
+        .iter()
+        .map(|entry| ZenohAttachmentItem {
+            key: entry.key.as_ptr(),
+            value: data[entry.value.clone()].as_ptr(),
+            value_len: entry.value.len(),
+        })
+        .collect()
+}
+
+struct AttachmentWrapper {
+    _data: Vec<u8>,
+    _entries: Vec<AttachmentEntry>,
+    items: Vec<ZenohAttachmentItem>,
+}
+
+/// Parses attachment bytes in the key_len|key|value_len|value format
+/// (as produced by zenoh_put_with_attachment and delivered in SampleData).
+/// On success, writes the items pointer and count to out_items/out_count and
+/// returns a handle that owns them; free it with zenoh_attachment_free.
+/// Returns NULL on failure.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_attachment_parse(
+    data: *const u8,
+    data_len: usize,
+    out_items: *mut *const ZenohAttachmentItem,
+    out_count: *mut usize,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if data.is_null() && data_len > 0 {
+            set_error("Data pointer is null but length > 0");
+            return ptr::null_mut();
+        }
+        if out_items.is_null() || out_count.is_null() {
+            set_error("Output pointer is null");
+            return ptr::null_mut();
+        }
+
+        let bytes = if data.is_null() || data_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(data, data_len) }.to_vec()
+        };
+
+        let entries = match decode_attachment(&bytes) {
+            Ok(entries) => entries,
+            Err(e) => {
+                set_error(e);
+                return ptr::null_mut();
+            }
+        };
+        let items = attachment_items(&bytes, &entries);
+        let handle = Box::new(AttachmentWrapper {
+            _data: bytes,
+            _entries: entries,
+            items,
+        });
+
+        unsafe {
+            *out_items = handle.items.as_ptr();
+            *out_count = handle.items.len();
+        }
+        Box::into_raw(handle) as *mut c_void
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_attachment_parse");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a parsed attachment returned by zenoh_attachment_parse.
+#[no_mangle]
+pub extern "C" fn zenoh_attachment_free(attachment: *mut c_void) {
+    if attachment.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(attachment as *mut AttachmentWrapper);
+        }
+    });
+}
+
+// ============== Sample Conversion ==============
+
+/// Owned C representations of a sample's fields, borrowing the sample where possible.
+/// SampleData views built from it stay valid as long as the storage lives.
+struct SampleStorage<'a> {
+    sample: &'a Sample,
+    key: CString,
+    payload: std::borrow::Cow<'a, [u8]>,
+    kind: ZenohSampleKind,
+    encoding_id: ZenohEncodingId,
+    encoding: CString,
+    timestamp: Option<ZenohTimestamp>,
+    attachment: Option<std::borrow::Cow<'a, [u8]>>,
+    /// Decoded attachment entries; `attachment_items` points into them and `attachment`.
+    _entries: Vec<AttachmentEntry>,
+    attachment_items: Vec<ZenohAttachmentItem>,
+}
+
+impl<'a> SampleStorage<'a> {
+    /// Returns None if the key expression cannot be converted to a C string.
+    fn new(sample: &'a Sample) -> Option<Self> {
+        let key = CString::new(sample.key_expr().as_str()).ok()?;
+
+        let kind = match sample.kind() {
+            SampleKind::Put => ZenohSampleKind::Put,
+            SampleKind::Delete => ZenohSampleKind::Delete,
+        };
+
+        let timestamp = sample.timestamp().map(to_ffi_timestamp);
+
+        // Attachments that are not in the FFI format are still delivered as raw bytes
+        let attachment = sample.attachment().map(|a| a.to_bytes());
+        let entries = attachment
+            .as_deref()
+            .map(|bytes| decode_attachment(bytes).unwrap_or_default())
+            .unwrap_or_default();
+        let attachment_items = attachment_items(attachment.as_deref().unwrap_or(&[]), &entries);
+
+        Some(SampleStorage {
+            sample,
+            key,
+            payload: sample.payload().to_bytes(),
+            kind,
+            encoding_id: encoding_to_id(sample.encoding()),
+            encoding: CString::new(sample.encoding().to_string()).unwrap_or_default(),
+            timestamp,
+            attachment,
+            _entries: entries,
+            attachment_items,
+        })
+    }
+
+    fn sample_data(&self) -> SampleData {
+        let attachment_bytes: &[u8] = self.attachment.as_deref().unwrap_or(&[]);
+        SampleData {
+            key_expr: self.key.as_ptr(),
+            payload_data: self.payload.as_ptr(),
+            payload_len: self.payload.len(),
+            kind: self.kind,
+            encoding_id: self.encoding_id,
+            timestamp_valid: self.timestamp.is_some(),
+            timestamp: self.timestamp.unwrap_or(ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] }),
+            encoding: self.encoding.as_ptr(),
+            attachment_data: if self.attachment.is_some() { attachment_bytes.as_ptr() } else { ptr::null() },
+            attachment_len: attachment_bytes.len(),
+            attachment_items: if self.attachment_items.is_empty() {
+                ptr::null()
+            } else {
+                self.attachment_items.as_ptr()
+            },
+            attachment_count: self.attachment_items.len(),
+            sample: self.sample as *const Sample as *const ZenohSampleRef,
+        }
+    }
+}
+
+/// Builds a SampleData view of the sample and passes it to `f`.
+/// All pointers in the SampleData are valid only during `f`.
+/// Returns None if the key expression cannot be converted to a C string.
+fn with_sample_data<R>(sample: &Sample, f: impl FnOnce(&SampleData) -> R) -> Option<R> {
+    let storage = SampleStorage::new(sample)?;
+    Some(f(&storage.sample_data()))
+}
+
+/// Builds a contiguous SampleData array for the samples and passes it to `f`.
+/// Samples whose key expression cannot be converted to a C string are skipped.
+/// All pointers in the array are valid only during `f`.
+fn with_sample_data_batch<R>(samples: &[Sample], f: impl FnOnce(&[SampleData]) -> R) -> R {
+    let storages: Vec<SampleStorage> = samples.iter().filter_map(SampleStorage::new).collect();
+    let data: Vec<SampleData> = storages.iter().map(SampleStorage::sample_data).collect();
+    f(&data)
+}
+
+/// Builds a ReplyErrorData view of the error reply and passes it to `f`.
+/// All pointers in the ReplyErrorData are valid only during `f`.
+fn with_reply_error_data<R>(reply: &Reply, err: &ReplyError, f: impl FnOnce(&ReplyErrorData) -> R) -> R {
+    let payload = err.payload().to_bytes();
+    let encoding_cstr = CString::new(err.encoding().to_string()).unwrap_or_default();
+
+    let (replier_id_valid, replier_id) = match reply.replier_id() {
+        Some(zid) => {
+            let id_bytes = zid.to_le_bytes();
+            let mut id = [0u8; 16];
+            id.copy_from_slice(&id_bytes[..16.min(id_bytes.len())]);
+            (true, id)
+        }
+        None => (false, [0u8; 16]),
+    };
+
+    let c_error = ReplyErrorData {
+        payload_data: payload.as_ptr(),
+        payload_len: payload.len(),
+        encoding_id: encoding_to_id(err.encoding()),
+        encoding: encoding_cstr.as_ptr(),
+        replier_id_valid,
+        replier_id,
+    };
+
+    f(&c_error)
+}
+
+/// Delivers a reply to the sample callback or, for error replies, to the error callback.
+fn deliver_reply(
+    reply: &Reply,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
+) {
+    match reply.result() {
+        Ok(sample) => {
+            with_sample_data(sample, |c_sample| unsafe {
+                callback(c_sample, context);
+            });
+        }
+        Err(err) => {
+            if let Some(error_callback) = error_callback {
+                with_reply_error_data(reply, err, |c_error| unsafe {
+                    error_callback(c_error, context);
+                });
+            }
+        }
+    }
+}
+
+// ============== Put with Encoding ==============
+
+/// Publishes data with encoding on the given publisher.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_put_with_encoding(
+    publisher: *mut c_void,
+    payload: *const u8,
+    payload_len: usize,
+    encoding_id: ZenohEncodingId,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        let encoding = id_to_encoding(encoding_id);
+
+        let put_result = run_blocking(async move {
+            handle.publisher.put(data).encoding(encoding).await
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with encoding failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_publisher_put_with_encoding");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Puts data directly on a key expression with encoding.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_put_with_encoding(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    encoding_id: ZenohEncodingId,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        let encoding = id_to_encoding(encoding_id);
+
+        let put_result = run_blocking(async move {
+            handle.session.put(key, data).encoding(encoding).await
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with encoding failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_put_with_encoding");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Publishes data on the given publisher with a full encoding string
+/// (e.g. "application/protobuf;my.Message"). Pass NULL for the default encoding.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_put_with_encoding_str(
+    publisher: *mut c_void,
+    payload: *const u8,
+    payload_len: usize,
+    encoding: *const c_char,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        let encoding = match resolve_encoding(ZenohEncodingId::Empty, encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
+        };
+
+        let put_result = run_blocking(async move {
+            handle.publisher.put(data).encoding(encoding).await
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with encoding failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_publisher_put_with_encoding_str");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Puts data directly on a key expression with a full encoding string
+/// (e.g. "application/x-ros2-cdr"). Pass NULL for the default encoding.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_put_with_encoding_str(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    encoding: *const c_char,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        let encoding = match resolve_encoding(ZenohEncodingId::Empty, encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
+        };
+
+        let put_result = run_blocking(async move {
+            handle.session.put(key, data).encoding(encoding).await
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with encoding failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_put_with_encoding_str");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Put with Attachment ==============
+
+/// Puts data with attachment on a key expression.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_put_with_attachment(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    attachment_items: *const ZenohAttachmentItem,
+    attachment_count: usize,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        // Build attachment as serialized bytes
+        let attachment_bytes = serialize_attachment(attachment_items, attachment_count);
+
+        let put_result = run_blocking(async move {
+            if let Some(att_bytes) = attachment_bytes {
+                handle.session.put(key, data).attachment(att_bytes).await
+            } else {
+                handle.session.put(key, data).await
+            }
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with attachment failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_put_with_attachment");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Unified Put ==============
+
+/// Creates default put options
+#[no_mangle]
+pub extern "C" fn zenoh_put_options_default() -> PutOptions {
+    PutOptions {
+        encoding_id: ZenohEncodingId::Empty,
+        encoding: ptr::null(),
+        attachment_items: ptr::null(),
+        attachment_count: 0,
+        congestion_control: ZenohCongestionControl::Drop,
+        priority: ZenohPriority::Data,
+        is_express: false,
+        timestamp_valid: false,
+        timestamp: ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] },
+        allowed_destination: ZenohLocality::Any,
+    }
+}
+
+/// Puts data on a key expression with encoding, attachment, QoS and timestamp options.
+/// Pass NULL options for defaults.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_put_with_options(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    payload: *const u8,
+    payload_len: usize,
+    options: *const PutOptions,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ZenohError::NullPointer;
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+
+        put_with_options_impl(&handle.session, key, payload, payload_len, options)
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_put_with_options");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Puts data with options on a key expression string or declared KeyExpr.
+/// The payload pointer must already have been checked against its length.
+fn put_with_options_impl<K>(
+    session: &Session,
+    key: K,
+    payload: *const u8,
+    payload_len: usize,
+    options: *const PutOptions,
+) -> ZenohError
+where
+    K: TryInto<KeyExpr<'static>> + Send,
+    K::Error: Into<zenoh::Error>,
+{
+    let data = if payload.is_null() || payload_len == 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+    };
+
+    let opts = if options.is_null() {
+        zenoh_put_options_default()
+    } else {
+        unsafe { *options }
+    };
+
+    let encoding = match resolve_encoding(opts.encoding_id, opts.encoding) {
+        Ok(enc) => enc,
+        Err(e) => {
+            set_error(e);
+            return ZenohError::InvalidEncoding;
+        }
+    };
+    let attachment_bytes = serialize_attachment(opts.attachment_items, opts.attachment_count);
+    let timestamp = if opts.timestamp_valid {
+        Some(to_timestamp(session, &opts.timestamp))
+    } else {
+        None
+    };
+
+    let put_result = run_blocking_local(async {
+        let mut builder = session
+            .put(key, data)
+            .encoding(encoding)
+            .congestion_control(to_congestion_control(opts.congestion_control))
+            .priority(to_priority(opts.priority))
+            .express(opts.is_express)
+            .allowed_destination(to_locality(opts.allowed_destination));
+        if let Some(att_bytes) = attachment_bytes {
+            builder = builder.attachment(att_bytes);
+        }
+        if let Some(ts) = timestamp {
+            builder = builder.timestamp(ts);
+        }
+        builder.await
+    });
+
+    match put_result {
+        Ok(_) => ZenohError::Ok,
+        Err(e) => {
+            set_error(format!("Put with options failed: {}", e));
+            ZenohError::PutFailed
+        }
+    }
+}
+
+/// Creates default publisher put options
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_put_options_default() -> PublisherPutOptions {
+    PublisherPutOptions {
+        encoding_id: ZenohEncodingId::Empty,
+        encoding: ptr::null(),
+        attachment_items: ptr::null(),
+        attachment_count: 0,
+        timestamp_valid: false,
+        timestamp: ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] },
+    }
+}
+
+/// Publishes data on the given publisher with encoding, attachment and timestamp options.
+/// Pass NULL options for defaults.
+/// QoS is configured when the publisher is declared (see zenoh_declare_publisher_with_options).
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_publisher_put_with_options(
+    publisher: *mut c_void,
+    payload: *const u8,
+    payload_len: usize,
+    options: *const PublisherPutOptions,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if publisher.is_null() {
+            set_error("Publisher pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        if payload.is_null() && payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(publisher as *const PublisherWrapper) };
+        let data = if payload.is_null() || payload_len == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(payload, payload_len) }.to_vec()
+        };
+
+        let opts = if options.is_null() {
+            zenoh_publisher_put_options_default()
+        } else {
+            unsafe { *options }
+        };
+
+        let encoding = match resolve_encoding(opts.encoding_id, opts.encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
+        };
+        let attachment_bytes = serialize_attachment(opts.attachment_items, opts.attachment_count);
+        let timestamp = if opts.timestamp_valid {
+            Some(to_timestamp(&handle._session, &opts.timestamp))
+        } else {
+            None
+        };
+
+        let put_result = run_blocking(async move {
+            let mut builder = handle.publisher.put(data).encoding(encoding);
+            if let Some(att_bytes) = attachment_bytes {
+                builder = builder.attachment(att_bytes);
+            }
+            if let Some(ts) = timestamp {
+                builder = builder.timestamp(ts);
+            }
+            builder.await
+        });
+
+        match put_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Put with options failed: {}", e));
+                ZenohError::PutFailed
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_publisher_put_with_options");
+            ZenohError::Panic
+        }
+    }
+}
+
+// ============== Querier ==============
+
+/// Declares a querier for repeated queries on the same key expression.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_querier(
+    session: *mut c_void,
+    key_expr: *const c_char,
+) -> *mut c_void {
+    zenoh_declare_querier_with_options(session, key_expr, ptr::null())
+}
+
+/// Creates default querier options
+#[no_mangle]
+pub extern "C" fn zenoh_querier_options_default() -> QuerierOptions {
+    QuerierOptions {
+        target: ZenohQueryTarget::BestMatching,
+        consolidation: ZenohConsolidationMode::Auto,
+        timeout_ms: 0,
+        congestion_control: ZenohCongestionControl::Block,
+        priority: ZenohPriority::Data,
+        is_express: false,
+        allowed_destination: ZenohLocality::Any,
+    }
+}
+
+/// Declares a querier with target, consolidation, timeout, QoS and destination options.
+/// Pass NULL options for defaults.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_declare_querier_with_options(
+    session: *mut c_void,
+    key_expr: *const c_char,
+    options: *const QuerierOptions,
+) -> *mut c_void {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if session.is_null() {
+            set_error("Session pointer is null");
+            return ptr::null_mut();
+        }
+        if key_expr.is_null() {
+            set_error("Key expression is null");
+            return ptr::null_mut();
+        }
+
+        let handle = unsafe { &*(session as *const SessionWrapper) };
+        let key = unsafe {
+            match CStr::from_ptr(key_expr).to_str() {
+                Ok(s) => s,
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in key expression: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        let opts = if options.is_null() {
+            zenoh_querier_options_default()
+        } else {
+            unsafe { *options }
+        };
+
+        let session_arc = handle.session.clone();
+        let querier_result = run_blocking(async move {
+            let mut builder = handle.session
+                .declare_querier(key)
+                .target(to_query_target(opts.target))
+                .consolidation(to_consolidation(opts.consolidation))
+                .congestion_control(to_congestion_control(opts.congestion_control))
+                .priority(to_priority(opts.priority))
+                .express(opts.is_express)
+                .allowed_destination(to_locality(opts.allowed_destination));
+            if opts.timeout_ms > 0 {
+                builder = builder.timeout(Duration::from_millis(opts.timeout_ms));
+            }
+            builder.await
+        });
+
+        match querier_result {
+            Ok(querier) => {
+                let static_querier: zenoh::query::Querier<'static> = unsafe {
+                    std::mem::transmute(querier)
+                };
+                let (dispatcher, dispatch_guard) = handle.dispatch.dispatcher();
+                let q_handle = Box::new(QuerierWrapper {
+                    querier: static_querier,
+                    _session: session_arc,
+                    dispatch: handle.dispatch.clone(),
+                    dispatcher,
+                    _dispatch_guard: dispatch_guard,
+                });
+                Box::into_raw(q_handle) as *mut c_void
+            }
+            Err(e) => {
+                set_error(format!("Failed to declare querier: {}", e));
+                ptr::null_mut()
+            }
+        }
+    });
+    
+    match result {
+        Ok(ptr) => ptr,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_declare_querier_with_options");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Performs a get query using the querier.
+/// The callback receives SampleData pointers that are valid only during the callback invocation.
+/// Do not store these pointers for later use.
+/// Error replies are discarded; use zenoh_querier_get_with_error_callback to receive them.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_querier_get(
+    querier: *mut c_void,
+    callback: ZenohGetCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    zenoh_querier_get_with_error_callback(querier, callback, None, context)
+}
+
+/// Performs a get query using the querier, delivering error replies to `error_callback`.
+/// The callbacks receive pointers that are valid only during the callback invocation.
+/// Do not store these pointers for later use.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_querier_get_with_error_callback(
+    querier: *mut c_void,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if querier.is_null() {
+            set_error("Querier pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(querier as *const QuerierWrapper) };
+        let dispatcher = handle.dispatcher.clone();
+        let context_ptr = context as usize;
+
+        let get_result = run_blocking_local(async {
+            handle.querier
+                .get()
+                .callback(move |reply| {
+                    dispatcher.dispatch(move || {
+                        deliver_reply(&reply, callback, error_callback, context_ptr as *mut c_void);
+                    });
+                })
+                .await
+        });
+
+        match get_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Querier get failed: {}", e));
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_querier_get_with_error_callback");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Creates default querier get options
+#[no_mangle]
+pub extern "C" fn zenoh_querier_get_options_default() -> QuerierGetOptions {
+    QuerierGetOptions {
+        parameters: ptr::null(),
+        payload: ptr::null(),
+        payload_len: 0,
+        encoding_id: ZenohEncodingId::Empty,
+        encoding: ptr::null(),
+        attachment_items: ptr::null(),
+        attachment_count: 0,
+    }
+}
+
+/// Performs a get query using the querier with selector parameters, payload,
+/// encoding and attachment. Pass NULL options for a bare get.
+/// Error replies are delivered to `error_callback`.
+/// The callbacks receive pointers that are valid only during the callback invocation.
+/// Do not store these pointers for later use.
+/// Call zenoh_last_error() for error details.
+#[no_mangle]
+pub extern "C" fn zenoh_querier_get_with_options(
+    querier: *mut c_void,
+    options: *const QuerierGetOptions,
+    callback: ZenohGetCallback,
+    error_callback: ZenohReplyErrorCallback,
+    context: *mut c_void,
+) -> ZenohError {
+    clear_error();
+    
+    let result = panic::catch_unwind(|| {
+        if querier.is_null() {
+            set_error("Querier pointer is null");
+            return ZenohError::NullPointer;
+        }
+
+        let handle = unsafe { &*(querier as *const QuerierWrapper) };
+        let opts = if options.is_null() {
+            zenoh_querier_get_options_default()
+        } else {
+            unsafe { *options }
+        };
+
+        let parameters = if opts.parameters.is_null() {
+            None
+        } else {
+            match unsafe { CStr::from_ptr(opts.parameters) }.to_str() {
+                Ok(s) => Some(s.to_string()),
+                Err(e) => {
+                    set_error(format!("Invalid UTF-8 in parameters: {}", e));
+                    return ZenohError::InvalidKeyExpr;
+                }
+            }
+        };
+        if opts.payload.is_null() && opts.payload_len > 0 {
+            set_error("Payload pointer is null but length > 0");
+            return ZenohError::NullPointer;
+        }
+        let payload = if opts.payload.is_null() {
+            None
+        } else {
+            Some(unsafe { std::slice::from_raw_parts(opts.payload, opts.payload_len) }.to_vec())
+        };
+        let encoding = match resolve_encoding(opts.encoding_id, opts.encoding) {
+            Ok(enc) => enc,
+            Err(e) => {
+                set_error(e);
+                return ZenohError::InvalidEncoding;
+            }
+        };
+        let attachment_bytes = serialize_attachment(opts.attachment_items, opts.attachment_count);
+
+        let dispatcher = handle.dispatcher.clone();
+        let context_ptr = context as usize;
+
+        let get_result = run_blocking_local(async {
+            let mut builder = handle.querier.get();
+            if let Some(params) = parameters {
+                builder = builder.parameters(params);
+            }
+            if let Some(data) = payload {
+                builder = builder.payload(data).encoding(encoding);
+            }
+            if let Some(att_bytes) = attachment_bytes {
+                builder = builder.attachment(att_bytes);
+            }
+            builder
+                .callback(move |reply| {
+                    dispatcher.dispatch(move || {
+                        deliver_reply(&reply, callback, error_callback, context_ptr as *mut c_void);
+                    });
+                })
+                .await
+        });
+
+        match get_result {
+            Ok(_) => ZenohError::Ok,
+            Err(e) => {
+                set_error(format!("Querier get failed: {}", e));
+                ZenohError::Unknown
+            }
+        }
+    });
+    
+    match result {
+        Ok(err) => err,
+        Err(_) => {
+            set_error("Panic occurred in zenoh_querier_get_with_options");
+            ZenohError::Panic
+        }
+    }
+}
+
+/// Undeclares and frees a querier.
+#[no_mangle]
+pub extern "C" fn zenoh_undeclare_querier(querier: *mut c_void) {
+    if querier.is_null() {
+        return;
+    }
+    let _ = panic::catch_unwind(|| {
+        unsafe {
+            let _ = Box::from_raw(querier as *mut QuerierWrapper);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    thread_local! {
+        /// Heap allocations made by the current thread.
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// System allocator that counts allocations per thread, so tests can assert that
+    /// a code path does not allocate.
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    /// Polls `cond` until it holds or `timeout` elapses; returns the last result.
+    fn wait_until(timeout: Duration, mut cond: impl FnMut() -> bool) -> bool {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if cond() {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_session_lifecycle() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_publisher_lifecycle() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        let key = CString::new("test/key").unwrap();
+        let publisher = zenoh_declare_publisher(session, key.as_ptr());
+        assert!(!publisher.is_null());
+
+        let data = b"test data";
+        let result = zenoh_publisher_put(publisher, data.as_ptr(), data.len());
+        assert!(matches!(result, ZenohError::Ok));
+
+        zenoh_undeclare_publisher(publisher);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_subscriber_lifecycle() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn test_callback(_sample: *const SampleData, _context: *mut c_void) {}
+
+        let key = CString::new("test/key").unwrap();
+        let subscriber = zenoh_declare_subscriber(
+            session,
+            key.as_ptr(),
+            test_callback,
+            ptr::null_mut(),
+        );
+        assert!(!subscriber.is_null());
+
+        zenoh_undeclare_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_attachment_roundtrip() {
+        let k1 = CString::new("trace-id").unwrap();
+        let k2 = CString::new("empty").unwrap();
+        let v1 = b"abc";
+        let items = [
+            ZenohAttachmentItem { key: k1.as_ptr(), value: v1.as_ptr(), value_len: v1.len() },
+            ZenohAttachmentItem { key: k2.as_ptr(), value: ptr::null(), value_len: 0 },
+        ];
+        let bytes = serialize_attachment(items.as_ptr(), items.len()).unwrap();
+
+        let mut out_items: *const ZenohAttachmentItem = ptr::null();
+        let mut out_count = 0usize;
+        let parsed = zenoh_attachment_parse(bytes.as_ptr(), bytes.len(), &mut out_items, &mut out_count);
+        assert!(!parsed.is_null());
+        assert_eq!(out_count, 2);
+
+        let decoded = unsafe { std::slice::from_raw_parts(out_items, out_count) };
+        unsafe {
+            assert_eq!(CStr::from_ptr(decoded[0].key).to_str().unwrap(), "trace-id");
+            assert_eq!(std::slice::from_raw_parts(decoded[0].value, decoded[0].value_len), v1);
+            assert_eq!(CStr::from_ptr(decoded[1].key).to_str().unwrap(), "empty");
+        }
+        assert_eq!(decoded[1].value_len, 0);
+        zenoh_attachment_free(parsed);
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(decode_attachment(truncated).is_err());
+    }
+
+    #[test]
+    fn test_encoding_string_roundtrip() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_sample(sample: *const SampleData, context: *mut c_void) {
+            let received = unsafe { &*(context as *const Mutex<Vec<String>>) };
+            let encoding = unsafe { CStr::from_ptr((*sample).encoding) };
+            received.lock().unwrap().push(encoding.to_string_lossy().into_owned());
+        }
+
+        let received: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let key = CString::new("test/encoding/put").unwrap();
+        let subscriber = zenoh_declare_subscriber(
+            session,
+            key.as_ptr(),
+            on_sample,
+            &received as *const Mutex<Vec<String>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        let protobuf = CString::new("application/protobuf;my.Message").unwrap();
+        let cdr = CString::new("application/x-ros2-cdr").unwrap();
+        let data = b"payload";
+        let result =
+            zenoh_put_with_encoding_str(session, key.as_ptr(), data.as_ptr(), data.len(), protobuf.as_ptr());
+        assert!(matches!(result, ZenohError::Ok));
+
+        let publisher = zenoh_declare_publisher(session, key.as_ptr());
+        assert!(!publisher.is_null());
+        let result = zenoh_publisher_put_with_encoding_str(publisher, data.as_ptr(), data.len(), cdr.as_ptr());
+        assert!(matches!(result, ZenohError::Ok));
+
+        assert!(wait_until(Duration::from_secs(5), || received.lock().unwrap().len() >= 2));
+        assert_eq!(
+            *received.lock().unwrap(),
+            ["application/protobuf;my.Message", "application/x-ros2-cdr"]
+        );
+        zenoh_undeclare_publisher(publisher);
+        zenoh_undeclare_subscriber(subscriber);
+
+        extern "C" fn on_query(query: *mut c_void, _context: *mut c_void) {
+            let key = CString::new("test/encoding/query").unwrap();
+            let encoding = CString::new("application/protobuf;my.Reply").unwrap();
+            let data = b"reply";
+            zenoh_query_reply_with_encoding(query, key.as_ptr(), data.as_ptr(), data.len(), encoding.as_ptr());
+            zenoh_query_finalize(query);
+        }
+
+        let query_key = CString::new("test/encoding/query").unwrap();
+        let queryable = zenoh_declare_queryable(session, query_key.as_ptr(), on_query, ptr::null_mut());
+        assert!(!queryable.is_null());
+
+        let replies: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        let result = zenoh_get_with_options(
+            session,
+            query_key.as_ptr(),
+            ptr::null(),
+            on_sample,
+            None,
+            &replies as *const Mutex<Vec<String>> as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+        assert_eq!(*replies.lock().unwrap(), ["application/protobuf;my.Reply"]);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_get_with_options_payload() {
+        #[derive(Default)]
+        struct Received {
+            payload: Vec<u8>,
+            encoding: String,
+            attachment: Vec<u8>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let received = unsafe { &*(context as *const Mutex<Received>) };
+            let mut received = received.lock().unwrap();
+            let mut len = 0usize;
+            let data = zenoh_query_payload(query, &mut len);
+            if !data.is_null() {
+                received.payload = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+            }
+            let encoding = zenoh_query_encoding(query);
+            if !encoding.is_null() {
+                received.encoding = unsafe { CStr::from_ptr(encoding) }.to_string_lossy().into_owned();
+                zenoh_free_string(encoding);
+            }
+            let data = zenoh_query_attachment(query, &mut len);
+            if !data.is_null() {
+                received.attachment = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+            }
+
+            let key = CString::new("test/getopts").unwrap();
+            let reply = b"pong";
+            zenoh_query_reply(query, key.as_ptr(), reply.as_ptr(), reply.len());
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(sample: *const SampleData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<Vec<Vec<u8>>>) };
+            let sample = unsafe { &*sample };
+            let payload = unsafe { std::slice::from_raw_parts(sample.payload_data, sample.payload_len) };
+            replies.lock().unwrap().push(payload.to_vec());
+        }
+
+        let received: Mutex<Received> = Mutex::new(Received::default());
+        let key = CString::new("test/getopts").unwrap();
+        let queryable = zenoh_declare_queryable(
+            session,
+            key.as_ptr(),
+            on_query,
+            &received as *const Mutex<Received> as *mut c_void,
+        );
+        assert!(!queryable.is_null());
+
+        let att_key = CString::new("trace-id").unwrap();
+        let att_value = b"7";
+        let items = [ZenohAttachmentItem {
+            key: att_key.as_ptr(),
+            value: att_value.as_ptr(),
+            value_len: att_value.len(),
+        }];
+        let encoding = CString::new("text/plain;charset=utf-8").unwrap();
+        let payload = b"ping";
+        let mut options = zenoh_get_options_default();
+        options.target = ZenohQueryTarget::All;
+        options.timeout_ms = 2000;
+        options.payload = payload.as_ptr();
+        options.payload_len = payload.len();
+        options.encoding = encoding.as_ptr();
+        options.attachment_items = items.as_ptr();
+        options.attachment_count = items.len();
+
+        let replies: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+        let result = zenoh_get_with_options(
+            session,
+            key.as_ptr(),
+            &options,
+            on_reply,
+            None,
+            &replies as *const Mutex<Vec<Vec<u8>>> as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+        assert_eq!(*replies.lock().unwrap(), [b"pong".to_vec()]);
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.payload, payload);
+        assert_eq!(received.encoding, "text/plain;charset=utf-8");
+        let entries = decode_attachment(&received.attachment).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key.to_str().unwrap(), "trace-id");
+        assert_eq!(&received.attachment[entries[0].value.clone()], att_value);
+        drop(received);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_get_async_done_callback() {
+        #[derive(Default)]
+        struct AsyncState {
+            replies: AtomicUsize,
+            done_calls: AtomicUsize,
+            done_ok: AtomicBool,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, _context: *mut c_void) {
+            let key = CString::new("test/async").unwrap();
+            let data = b"v";
+            zenoh_query_reply(query, key.as_ptr(), data.as_ptr(), data.len());
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, context: *mut c_void) {
+            let state = unsafe { &*(context as *const AsyncState) };
+            state.replies.fetch_add(1, Ordering::SeqCst);
+        }
+        extern "C" fn on_done(status: ZenohError, _message: *const c_char, context: *mut c_void) {
+            let state = unsafe { &*(context as *const AsyncState) };
+            state.done_ok.store(matches!(status, ZenohError::Ok), Ordering::SeqCst);
+            state.done_calls.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let key = CString::new("test/async").unwrap();
+        let queryable = zenoh_declare_queryable(session, key.as_ptr(), on_query, ptr::null_mut());
+        assert!(!queryable.is_null());
+
+        let state = AsyncState::default();
+        let context = &state as *const AsyncState as *mut c_void;
+        let result = zenoh_get_async(session, key.as_ptr(), ptr::null(), on_reply, None, on_done, context);
+        assert!(matches!(result, ZenohError::Ok));
+
+        assert!(wait_until(Duration::from_secs(10), || state.done_calls.load(Ordering::SeqCst) > 0));
+        assert_eq!(state.done_calls.load(Ordering::SeqCst), 1);
+        assert!(state.done_ok.load(Ordering::SeqCst));
+        assert_eq!(state.replies.load(Ordering::SeqCst), 1);
+
+        let result = zenoh_get_async(session, ptr::null(), ptr::null(), on_reply, None, on_done, context);
+        assert!(matches!(result, ZenohError::NullPointer));
+        assert_eq!(state.done_calls.load(Ordering::SeqCst), 1);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_get_reply_error() {
+        #[derive(Default)]
+        struct ErrorReplies {
+            samples: usize,
+            errors: Vec<(Vec<u8>, String, Option<[u8; 16]>)>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, _context: *mut c_void) {
+            let encoding = CString::new("text/plain;reason").unwrap();
+            let data = b"boom";
+            zenoh_query_reply_err(query, data.as_ptr(), data.len(), encoding.as_ptr());
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<ErrorReplies>) };
+            replies.lock().unwrap().samples += 1;
+        }
+        extern "C" fn on_error(error: *const ReplyErrorData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<ErrorReplies>) };
+            let error = unsafe { &*error };
+            let payload = unsafe { std::slice::from_raw_parts(error.payload_data, error.payload_len) }.to_vec();
+            let encoding = unsafe { CStr::from_ptr(error.encoding) }.to_string_lossy().into_owned();
+            let replier_id = error.replier_id_valid.then_some(error.replier_id);
+            replies.lock().unwrap().errors.push((payload, encoding, replier_id));
+        }
+
+        let key = CString::new("test/reply_err").unwrap();
+        let queryable = zenoh_declare_queryable(session, key.as_ptr(), on_query, ptr::null_mut());
+        assert!(!queryable.is_null());
+
+        let zid_bytes = unsafe { &*(session as *const SessionWrapper) }.session.zid().to_le_bytes();
+        let mut zid = [0u8; 16];
+        zid.copy_from_slice(&zid_bytes[..16]);
+
+        let replies: Mutex<ErrorReplies> = Mutex::new(ErrorReplies::default());
+        let context = &replies as *const Mutex<ErrorReplies> as *mut c_void;
+        let result = zenoh_get_with_error_callback(session, key.as_ptr(), on_reply, Some(on_error), context);
+        assert!(matches!(result, ZenohError::Ok));
+        {
+            let replies = replies.lock().unwrap();
+            assert_eq!(replies.samples, 0);
+            assert_eq!(replies.errors.len(), 1);
+            let (payload, encoding, replier_id) = &replies.errors[0];
+            assert_eq!(payload, b"boom");
+            assert_eq!(encoding, "text/plain;reason");
+            assert_eq!(*replier_id, Some(zid));
+        }
+
+        // Without an error callback the error reply is discarded
+        let result = zenoh_get(session, key.as_ptr(), on_reply, context);
+        assert!(matches!(result, ZenohError::Ok));
+        assert_eq!(replies.lock().unwrap().errors.len(), 1);
+        assert_eq!(replies.lock().unwrap().samples, 0);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_query_reply_del_and_err() {
+        #[derive(Default)]
+        struct Replies {
+            deletes: Vec<String>,
+            puts: usize,
+            errors: Vec<Vec<u8>>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, _context: *mut c_void) {
+            let key = CString::new("test/tombstone/a").unwrap();
+            zenoh_query_reply_del(query, key.as_ptr());
+            let data = b"gone";
+            zenoh_query_reply_err(query, data.as_ptr(), data.len(), ptr::null());
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(sample: *const SampleData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<Replies>) };
+            let sample = unsafe { &*sample };
+            let mut replies = replies.lock().unwrap();
+            match sample.kind {
+                ZenohSampleKind::Delete => {
+                    let key = unsafe { CStr::from_ptr(sample.key_expr) };
+                    replies.deletes.push(key.to_string_lossy().into_owned());
+                }
+                ZenohSampleKind::Put => replies.puts += 1,
+            }
+        }
+        extern "C" fn on_error(error: *const ReplyErrorData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<Replies>) };
+            let error = unsafe { &*error };
+            let payload = unsafe { std::slice::from_raw_parts(error.payload_data, error.payload_len) };
+            replies.lock().unwrap().errors.push(payload.to_vec());
+        }
+
+        let key = CString::new("test/tombstone/**").unwrap();
+        let queryable = zenoh_declare_queryable(session, key.as_ptr(), on_query, ptr::null_mut());
+        assert!(!queryable.is_null());
+
+        let replies: Mutex<Replies> = Mutex::new(Replies::default());
+        let mut options = zenoh_get_options_default();
+        options.consolidation = ZenohConsolidationMode::None;
+        let result = zenoh_get_with_options(
+            session,
+            key.as_ptr(),
+            &options,
+            on_reply,
+            Some(on_error),
+            &replies as *const Mutex<Replies> as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+
+        let replies = replies.lock().unwrap();
+        assert_eq!(replies.deletes, ["test/tombstone/a"]);
+        assert_eq!(replies.puts, 0);
+        assert_eq!(replies.errors, [b"gone".to_vec()]);
+        drop(replies);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_query_parameters() {
+        #[derive(Default)]
+        struct Introspection {
+            selector: String,
+            parameters: String,
+            b: Option<String>,
+            missing_is_null: bool,
+        }
+
+        fn take_string(s: *mut c_char) -> Option<String> {
+            if s.is_null() {
+                return None;
+            }
+            let value = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+            zenoh_free_string(s);
+            Some(value)
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let seen = unsafe { &*(context as *const Mutex<Introspection>) };
+            let mut seen = seen.lock().unwrap();
+            seen.selector = take_string(zenoh_query_selector(query)).unwrap_or_default();
+            seen.parameters = take_string(zenoh_query_parameters(query)).unwrap_or_default();
+            let name = CString::new("b").unwrap();
+            seen.b = take_string(zenoh_query_parameter(query, name.as_ptr()));
+            let name = CString::new("missing").unwrap();
+            seen.missing_is_null = zenoh_query_parameter(query, name.as_ptr()).is_null();
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, _context: *mut c_void) {}
+
+        let seen: Mutex<Introspection> = Mutex::new(Introspection::default());
+        let key = CString::new("test/introspect").unwrap();
+        let queryable = zenoh_declare_queryable(
+            session,
+            key.as_ptr(),
+            on_query,
+            &seen as *const Mutex<Introspection> as *mut c_void,
+        );
+        assert!(!queryable.is_null());
+
+        let selector = CString::new("test/introspect?a=1;b=two").unwrap();
+        let result = zenoh_get(session, selector.as_ptr(), on_reply, ptr::null_mut());
+        assert!(matches!(result, ZenohError::Ok));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.selector, "test/introspect");
+        assert_eq!(seen.parameters, "a=1;b=two");
+        assert_eq!(seen.b.as_deref(), Some("two"));
+        assert!(seen.missing_is_null);
+        drop(seen);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_query_reply_with_options() {
+        #[derive(Default)]
+        struct ReceivedReply {
+            payload_len: usize,
+            encoding_id: Option<u32>,
+            attachment: Vec<(String, Vec<u8>)>,
+            timestamp: Option<u64>,
+        }
+
+        const REPLY_TIME: u64 = 0x1234_5678_0000_0000;
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, _context: *mut c_void) {
+            let key = CString::new("test/reply_opts").unwrap();
+            let att_key = CString::new("source").unwrap();
+            let att_value = b"sensor-1";
+            let items = [ZenohAttachmentItem {
+                key: att_key.as_ptr(),
+                value: att_value.as_ptr(),
+                value_len: att_value.len(),
+            }];
+            let mut options = zenoh_reply_options_default();
+            options.encoding_id = ZenohEncodingId::AppJson;
+            options.attachment_items = items.as_ptr();
+            options.attachment_count = items.len();
+            options.timestamp_valid = true;
+            options.timestamp = ZenohTimestamp { time_ntp64: REPLY_TIME, id: [0u8; 16] };
+            options.priority = ZenohPriority::InteractiveHigh;
+            // An empty payload is a valid reply
+            zenoh_query_reply_with_options(query, key.as_ptr(), ptr::null(), 0, &options);
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(sample: *const SampleData, context: *mut c_void) {
+            let received = unsafe { &*(context as *const Mutex<Option<ReceivedReply>>) };
+            let sample = unsafe { &*sample };
+            let items = if sample.attachment_items.is_null() {
+                &[][..]
+            } else {
+                unsafe { std::slice::from_raw_parts(sample.attachment_items, sample.attachment_count) }
+            };
+            let attachment = items
+                .iter()
+                .map(|item| unsafe {
+                    (
+                        CStr::from_ptr(item.key).to_string_lossy().into_owned(),
+                        std::slice::from_raw_parts(item.value, item.value_len).to_vec(),
+                    )
+                })
+                .collect();
+            *received.lock().unwrap() = Some(ReceivedReply {
+                payload_len: sample.payload_len,
+                encoding_id: Some(sample.encoding_id as u32),
+                attachment,
+                timestamp: sample.timestamp_valid.then_some(sample.timestamp.time_ntp64),
+            });
+        }
+
+        let key = CString::new("test/reply_opts").unwrap();
+        let queryable = zenoh_declare_queryable(session, key.as_ptr(), on_query, ptr::null_mut());
+        assert!(!queryable.is_null());
+
+        let received: Mutex<Option<ReceivedReply>> = Mutex::new(None);
+        let result = zenoh_get(
+            session,
+            key.as_ptr(),
+            on_reply,
+            &received as *const Mutex<Option<ReceivedReply>> as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+
+        let received = received.lock().unwrap().take().expect("no reply received");
+        assert_eq!(received.payload_len, 0);
+        assert_eq!(received.encoding_id, Some(ZenohEncodingId::AppJson as u32));
+        assert_eq!(received.attachment, [("source".to_string(), b"sensor-1".to_vec())]);
+        assert_eq!(received.timestamp, Some(REPLY_TIME));
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_queryable_options() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let name = unsafe { CStr::from_ptr(context as *const c_char) }.to_bytes();
+            let key = CString::new("test/qopts").unwrap();
+            zenoh_query_reply(query, key.as_ptr(), name.as_ptr(), name.len());
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(sample: *const SampleData, context: *mut c_void) {
+            let replies = unsafe { &*(context as *const Mutex<Vec<String>>) };
+            let sample = unsafe { &*sample };
+            let payload = unsafe { std::slice::from_raw_parts(sample.payload_data, sample.payload_len) };
+            replies.lock().unwrap().push(String::from_utf8_lossy(payload).into_owned());
+        }
+
+        let key = CString::new("test/qopts").unwrap();
+        let complete_name = CString::new("complete").unwrap();
+        let partial_name = CString::new("partial").unwrap();
+        let remote_name = CString::new("remote-only").unwrap();
+
+        let mut options = zenoh_queryable_options_default();
+        options.complete = true;
+        let complete = zenoh_declare_queryable_with_options(
+            session,
+            key.as_ptr(),
+            &options,
+            on_query,
+            complete_name.as_ptr() as *mut c_void,
+        );
+        let partial = zenoh_declare_queryable_with_options(
+            session,
+            key.as_ptr(),
+            ptr::null(),
+            on_query,
+            partial_name.as_ptr() as *mut c_void,
+        );
+        let mut options = zenoh_queryable_options_default();
+        options.allowed_origin = ZenohLocality::Remote;
+        let remote = zenoh_declare_queryable_with_options(
+            session,
+            key.as_ptr(),
+            &options,
+            on_query,
+            remote_name.as_ptr() as *mut c_void,
+        );
+        assert!(!complete.is_null() && !partial.is_null() && !remote.is_null());
+
+        let collect = |target: ZenohQueryTarget| {
+            let replies: Mutex<Vec<String>> = Mutex::new(Vec::new());
+            let mut get_options = zenoh_get_options_default();
+            get_options.target = target;
+            get_options.consolidation = ZenohConsolidationMode::None;
+            let result = zenoh_get_with_options(
+                session,
+                key.as_ptr(),
+                &get_options,
+                on_reply,
+                None,
+                &replies as *const Mutex<Vec<String>> as *mut c_void,
+            );
+            assert!(matches!(result, ZenohError::Ok));
+            let mut replies = replies.into_inner().unwrap();
+            replies.sort();
+            replies
+        };
+
+        // The remote-only queryable never sees queries from its own session
+        assert_eq!(collect(ZenohQueryTarget::All), ["complete", "partial"]);
+        assert_eq!(collect(ZenohQueryTarget::AllComplete), ["complete"]);
+
+        zenoh_undeclare_queryable(remote);
+        zenoh_undeclare_queryable(partial);
+        zenoh_undeclare_queryable(complete);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_querier_get_with_options() {
+        #[derive(Default)]
+        struct QuerierSeen {
+            parameters: String,
+            payload: Vec<u8>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let seen = unsafe { &*(context as *const Mutex<QuerierSeen>) };
+            let parameters = zenoh_query_parameters(query);
+            let mut len = 0usize;
+            let data = zenoh_query_payload(query, &mut len);
+            {
+                let mut seen = seen.lock().unwrap();
+                if !parameters.is_null() {
+                    seen.parameters = unsafe { CStr::from_ptr(parameters) }.to_string_lossy().into_owned();
+                }
+                if !data.is_null() {
+                    seen.payload = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+                }
+            }
+            zenoh_free_string(parameters);
+
+            for key in ["test/querier/a", "test/querier/b"] {
+                let key = CString::new(key).unwrap();
+                let reply = b"ok";
+                zenoh_query_reply(query, key.as_ptr(), reply.as_ptr(), reply.len());
+            }
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, context: *mut c_void) {
+            let count = unsafe { &*(context as *const AtomicUsize) };
+            count.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let seen: Mutex<QuerierSeen> = Mutex::new(QuerierSeen::default());
+        let key = CString::new("test/querier/**").unwrap();
+        let queryable = zenoh_declare_queryable(
+            session,
+            key.as_ptr(),
+            on_query,
+            &seen as *const Mutex<QuerierSeen> as *mut c_void,
+        );
+        assert!(!queryable.is_null());
+
+        let mut options = zenoh_querier_options_default();
+        options.target = ZenohQueryTarget::All;
+        options.consolidation = ZenohConsolidationMode::None;
+        options.timeout_ms = 2000;
+        let querier = zenoh_declare_querier_with_options(session, key.as_ptr(), &options);
+        assert!(!querier.is_null());
+
+        let parameters = CString::new("x=1").unwrap();
+        let payload = b"q";
+        let mut get_options = zenoh_querier_get_options_default();
+        get_options.parameters = parameters.as_ptr();
+        get_options.payload = payload.as_ptr();
+        get_options.payload_len = payload.len();
+
+        let count = AtomicUsize::new(0);
+        let result = zenoh_querier_get_with_options(
+            querier,
+            &get_options,
+            on_reply,
+            None,
+            &count as *const AtomicUsize as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+        assert!(wait_until(Duration::from_secs(5), || count.load(Ordering::SeqCst) >= 2));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.parameters, "x=1");
+        assert_eq!(seen.payload, payload);
+        drop(seen);
+
+        zenoh_undeclare_querier(querier);
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_publisher_matching_status() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_matching(matching: bool, context: *mut c_void) {
+            let statuses = unsafe { &*(context as *const Mutex<Vec<bool>>) };
+            statuses.lock().unwrap().push(matching);
+        }
+        extern "C" fn on_sample(_sample: *const SampleData, _context: *mut c_void) {}
+
+        let key = CString::new("test/matching").unwrap();
+        let publisher = zenoh_declare_publisher(session, key.as_ptr());
+        assert!(!publisher.is_null());
+
+        let statuses: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+        let listener = zenoh_publisher_declare_matching_listener(
+            publisher,
+            on_matching,
+            &statuses as *const Mutex<Vec<bool>> as *mut c_void,
+        );
+        assert!(!listener.is_null());
+
+        let has_matching = |expected: bool| {
+            let mut matching = !expected;
+            let result = zenoh_publisher_has_matching_subscribers(publisher, &mut matching);
+            matches!(result, ZenohError::Ok) && matching == expected
+        };
+        assert!(has_matching(false));
+
+        let subscriber = zenoh_declare_subscriber(session, key.as_ptr(), on_sample, ptr::null_mut());
+        assert!(!subscriber.is_null());
+        assert!(wait_until(Duration::from_secs(5), || has_matching(true)));
+        assert!(wait_until(Duration::from_secs(5), || statuses.lock().unwrap().contains(&true)));
+
+        zenoh_undeclare_subscriber(subscriber);
+        assert!(wait_until(Duration::from_secs(5), || has_matching(false)));
+        assert!(wait_until(Duration::from_secs(5), || statuses.lock().unwrap().last() == Some(&false)));
+
+        zenoh_undeclare_matching_listener(listener);
+        zenoh_undeclare_publisher(publisher);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_liveliness_get() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_token(key_expr: *const c_char, is_alive: bool, context: *mut c_void) {
+            let keys = unsafe { &*(context as *const Mutex<Vec<String>>) };
+            if is_alive && !key_expr.is_null() {
+                let key = unsafe { CStr::from_ptr(key_expr) }.to_string_lossy().into_owned();
+                keys.lock().unwrap().push(key);
+            }
+        }
+
+        let key_a = CString::new("test/alive/a").unwrap();
+        let key_b = CString::new("test/alive/b").unwrap();
+        let token_a = zenoh_liveliness_declare_token(session, key_a.as_ptr());
+        let token_b = zenoh_liveliness_declare_token(session, key_b.as_ptr());
+        assert!(!token_a.is_null() && !token_b.is_null());
+
+        let pattern = CString::new("test/alive/**").unwrap();
+        let alive = || {
+            let keys: Mutex<Vec<String>> = Mutex::new(Vec::new());
+            let result = zenoh_liveliness_get(
+                session,
+                pattern.as_ptr(),
+                on_token,
+                &keys as *const Mutex<Vec<String>> as *mut c_void,
+                2000,
+            );
+            assert!(matches!(result, ZenohError::Ok));
+            let mut keys = keys.into_inner().unwrap();
+            keys.sort();
+            keys
+        };
+        assert_eq!(alive(), ["test/alive/a", "test/alive/b"]);
+
+        zenoh_liveliness_undeclare_token(token_b);
+        assert!(wait_until(Duration::from_secs(5), || alive() == ["test/alive/a"]));
+
+        zenoh_liveliness_undeclare_token(token_a);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_liveliness_subscriber_history() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_event(key_expr: *const c_char, is_alive: bool, context: *mut c_void) {
+            let events = unsafe { &*(context as *const Mutex<Vec<(String, bool)>>) };
+            if !key_expr.is_null() {
+                let key = unsafe { CStr::from_ptr(key_expr) }.to_string_lossy().into_owned();
+                events.lock().unwrap().push((key, is_alive));
+            }
+        }
+
+        let key = CString::new("test/history/node").unwrap();
+        let token = zenoh_liveliness_declare_token(session, key.as_ptr());
+        assert!(!token.is_null());
+
+        let pattern = CString::new("test/history/**").unwrap();
+        let with_history: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+        let without_history: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+        let mut options = zenoh_liveliness_subscriber_options_default();
+        options.history = true;
+        let history_sub = zenoh_liveliness_declare_subscriber_with_options(
+            session,
+            pattern.as_ptr(),
+            &options,
+            on_event,
+            &with_history as *const Mutex<Vec<(String, bool)>> as *mut c_void,
+        );
+        let plain_sub = zenoh_liveliness_declare_subscriber(
+            session,
+            pattern.as_ptr(),
+            on_event,
+            &without_history as *const Mutex<Vec<(String, bool)>> as *mut c_void,
+        );
+        assert!(!history_sub.is_null() && !plain_sub.is_null());
+
+        let node = ("test/history/node".to_string(), true);
+        assert!(wait_until(Duration::from_secs(5), || !with_history.lock().unwrap().is_empty()));
+        assert_eq!(*with_history.lock().unwrap(), [node.clone()]);
+
+        zenoh_liveliness_undeclare_token(token);
+        let gone = ("test/history/node".to_string(), false);
+        assert!(wait_until(Duration::from_secs(5), || !without_history.lock().unwrap().is_empty()));
+        // The subscriber without history only sees the token going away
+        assert_eq!(*without_history.lock().unwrap(), [gone.clone()]);
+        assert!(wait_until(Duration::from_secs(5), || with_history.lock().unwrap().len() == 2));
+        assert_eq!(*with_history.lock().unwrap(), [node, gone]);
+
+        zenoh_undeclare_subscriber(plain_sub);
+        zenoh_undeclare_subscriber(history_sub);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_query_multiple_replies() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let results = unsafe { &*(context as *const Mutex<Vec<bool>>) };
+            for key in ["test/multi/a", "test/multi/b"] {
+                let key = CString::new(key).unwrap();
+                let data = b"v";
+                let result = zenoh_query_reply(query, key.as_ptr(), data.as_ptr(), data.len());
+                results.lock().unwrap().push(matches!(result, ZenohError::Ok));
+            }
+            zenoh_query_finalize(query);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, context: *mut c_void) {
+            let count = unsafe { &*(context as *const AtomicUsize) };
+            count.fetch_add(1, Ordering::SeqCst);
+        }
+
+        let results: Mutex<Vec<bool>> = Mutex::new(Vec::new());
+        let key = CString::new("test/multi/**").unwrap();
+        let queryable = zenoh_declare_queryable(
+            session,
+            key.as_ptr(),
+            on_query,
+            &results as *const Mutex<Vec<bool>> as *mut c_void,
+        );
+        assert!(!queryable.is_null());
+
+        let count = AtomicUsize::new(0);
+        let mut options = zenoh_get_options_default();
+        options.consolidation = ZenohConsolidationMode::None;
+        let result = zenoh_get_with_options(
+            session,
+            key.as_ptr(),
+            &options,
+            on_reply,
+            None,
+            &count as *const AtomicUsize as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+        assert_eq!(*results.lock().unwrap(), [true, true]);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+
+        zenoh_undeclare_queryable(queryable);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_query_payload_outlives_queryable() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_query(query: *mut c_void, context: *mut c_void) {
+            let held = unsafe { &*(context as *const AtomicUsize) };
+            held.store(query as usize, Ordering::SeqCst);
+        }
+        extern "C" fn on_reply(_sample: *const SampleData, _context: *mut c_void) {}
+        extern "C" fn on_done(_status: ZenohError, _message: *const c_char, context: *mut c_void) {
+            let done = unsafe { &*(context as *const AtomicBool) };
+            done.store(true, Ordering::SeqCst);
+        }
+
+        let held = AtomicUsize::new(0);
+        let key = CString::new("test/held").unwrap();
+        let queryable = zenoh_declare_queryable(
+            session,
+            key.as_ptr(),
+            on_query,
+            &held as *const AtomicUsize as *mut c_void,
+        );
+        assert!(!queryable.is_null());
+
+        let payload = b"kept";
+        let encoding = CString::new("text/plain;kept").unwrap();
+        let mut options = zenoh_get_options_default();
+        options.payload = payload.as_ptr();
+        options.payload_len = payload.len();
+        options.encoding = encoding.as_ptr();
+        let done = AtomicBool::new(false);
+        let result = zenoh_get_async(
+            session,
+            key.as_ptr(),
+            &options,
+            on_reply,
+            None,
+            on_done,
+            &done as *const AtomicBool as *mut c_void,
+        );
+        assert!(matches!(result, ZenohError::Ok));
+        assert!(wait_until(Duration::from_secs(5), || held.load(Ordering::SeqCst) != 0));
+        let query = held.load(Ordering::SeqCst) as *mut c_void;
+
+        // Undeclaring the queryable finalizes the query but keeps its data readable
+        zenoh_undeclare_queryable(queryable);
+        let mut len = 0usize;
+        let data = zenoh_query_payload(query, &mut len);
+        assert!(!data.is_null());
+        assert_eq!(unsafe { std::slice::from_raw_parts(data, len) }, payload);
+        let query_encoding = zenoh_query_encoding(query);
+        assert!(!query_encoding.is_null());
+        assert_eq!(unsafe { CStr::from_ptr(query_encoding) }.to_str().unwrap(), "text/plain;kept");
+        zenoh_free_string(query_encoding);
+        let selector = zenoh_query_selector(query);
+        assert!(!selector.is_null());
+        assert_eq!(unsafe { CStr::from_ptr(selector) }.to_str().unwrap(), "test/held");
+        zenoh_free_string(selector);
+        assert!(matches!(
+            zenoh_query_reply(query, key.as_ptr(), payload.as_ptr(), payload.len()),
+            ZenohError::QueryFinalized
+        ));
+        zenoh_query_finalize(query);
+
+        assert!(wait_until(Duration::from_secs(10), || done.load(Ordering::SeqCst)));
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_pull_subscriber_ring() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        let key = CString::new("test/pull").unwrap();
+        let subscriber = zenoh_declare_pull_subscriber(session, key.as_ptr(), ZenohChannelKind::Ring, 2);
+        assert!(!subscriber.is_null());
+
+        for data in [b"1", b"2", b"3"] {
+            let result = zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len());
+            assert!(matches!(result, ZenohError::Ok));
         }
-    });
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let mut key_bufs = [[0 as c_char; 32]; 3];
+        let mut payload_bufs = [[0u8; 1]; 3];
+        let mut buffers: Vec<ZenohSampleBuffer> = key_bufs
+            .iter_mut()
+            .zip(payload_bufs.iter_mut())
+            .map(|(k, p)| ZenohSampleBuffer {
+                key_buffer: k.as_mut_ptr(),
+                key_capacity: k.len(),
+                payload_buffer: p.as_mut_ptr(),
+                payload_capacity: p.len(),
+                key_len: 0,
+                payload_len: 0,
+                kind: ZenohSampleKind::Put,
+                encoding_id: ZenohEncodingId::Empty,
+                timestamp_valid: false,
+                timestamp: ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] },
+            })
+            .collect();
+
+        // The ring keeps only the two most recent samples
+        let mut received = 0;
+        let result = zenoh_pull_subscriber_recv_batch(subscriber, buffers.as_mut_ptr(), buffers.len(), 1000, &mut received);
+        assert!(matches!(result, ZenohError::Ok));
+        assert_eq!(received, 2);
+        assert_eq!(payload_bufs[0][0], b'2');
+        assert_eq!(payload_bufs[1][0], b'3');
+        assert_eq!(unsafe { CStr::from_ptr(key_bufs[0].as_ptr()) }.to_str().unwrap(), "test/pull");
+
+        let mut got = true;
+        let result = zenoh_pull_subscriber_try_recv(subscriber, &mut buffers[2], &mut got);
+        assert!(matches!(result, ZenohError::Ok));
+        assert!(!got);
+
+        zenoh_undeclare_pull_subscriber(subscriber);
+        zenoh_close(session);
+    }
 
     #[test]
-    fn test_session_lifecycle() {
+    fn test_pull_subscriber_concurrent_receivers_keep_order() {
         let session = zenoh_open(ptr::null());
         assert!(!session.is_null());
+
+        const COUNT: u32 = 200;
+        let key = CString::new("test/pull/order").unwrap();
+        let subscriber = zenoh_declare_pull_subscriber(session, key.as_ptr(), ZenohChannelKind::Fifo, 0);
+        assert!(!subscriber.is_null());
+
+        // Every other sample is too large for a 4-byte buffer, so it is kept back for
+        // the next receive while the other receivers keep pulling from the channel
+        for i in 0..COUNT {
+            let mut data = i.to_le_bytes().to_vec();
+            data.resize(if i % 2 == 0 { 4 } else { 8 }, 0);
+            assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+        }
+
+        let subscriber_addr = subscriber as usize;
+        let taken = AtomicUsize::new(0);
+        let per_thread: Vec<Vec<u32>> = std::thread::scope(|scope| {
+            let workers: Vec<_> = (0..4)
+                .map(|_| {
+                    let taken = &taken;
+                    scope.spawn(move || {
+                        let mut seen = Vec::new();
+                        let mut key_buf = [0 as c_char; 64];
+                        let mut payload_buf = [0u8; 8];
+                        let mut attempt = 0usize;
+                        let deadline = Instant::now() + Duration::from_secs(10);
+                        while taken.load(Ordering::SeqCst) < COUNT as usize && Instant::now() < deadline {
+                            attempt += 1;
+                            let mut buffer = ZenohSampleBuffer {
+                                key_buffer: key_buf.as_mut_ptr(),
+                                key_capacity: key_buf.len(),
+                                payload_buffer: payload_buf.as_mut_ptr(),
+                                payload_capacity: if attempt % 2 == 0 { 4 } else { 8 },
+                                key_len: 0,
+                                payload_len: 0,
+                                kind: ZenohSampleKind::Put,
+                                encoding_id: ZenohEncodingId::Empty,
+                                timestamp_valid: false,
+                                timestamp: ZenohTimestamp { time_ntp64: 0, id: [0u8; 16] },
+                            };
+                            let mut got = false;
+                            let result = zenoh_pull_subscriber_recv_timeout(
+                                subscriber_addr as *const c_void,
+                                &mut buffer,
+                                10,
+                                &mut got,
+                            );
+                            if matches!(result, ZenohError::Ok) && got {
+                                let index = [payload_buf[0], payload_buf[1], payload_buf[2], payload_buf[3]];
+                                seen.push(u32::from_le_bytes(index));
+                                taken.fetch_add(1, Ordering::SeqCst);
+                            }
+                        }
+                        seen
+                    })
+                })
+                .collect();
+            workers.into_iter().map(|worker| worker.join().unwrap()).collect()
+        });
+
+        // Each receiver sees samples in publication order, and none is lost
+        for seen in &per_thread {
+            assert!(seen.windows(2).all(|pair| pair[0] < pair[1]), "out of order: {:?}", seen);
+        }
+        let mut all: Vec<u32> = per_thread.into_iter().flatten().collect();
+        all.sort_unstable();
+        assert_eq!(all, (0..COUNT).collect::<Vec<_>>());
+
+        zenoh_undeclare_pull_subscriber(subscriber);
         zenoh_close(session);
     }
 
     #[test]
-    fn test_publisher_lifecycle() {
+    fn test_dispatch_queue_pump() {
         let session = zenoh_open(ptr::null());
         assert!(!session.is_null());
+        assert!(matches!(zenoh_session_enable_dispatch_queue(session, 0), ZenohError::Ok));
 
-        let key = CString::new("test/key").unwrap();
-        let publisher = zenoh_declare_publisher(session, key.as_ptr());
-        assert!(!publisher.is_null());
+        extern "C" fn on_sample(_sample: *const SampleData, context: *mut c_void) {
+            let count = unsafe { &*(context as *const AtomicUsize) };
+            count.fetch_add(1, Ordering::SeqCst);
+        }
 
-        let data = b"test data";
-        let result = zenoh_publisher_put(publisher, data.as_ptr(), data.len());
-        assert!(matches!(result, ZenohError::Ok));
+        let count = AtomicUsize::new(0);
+        let key = CString::new("test/dispatch").unwrap();
+        let subscriber = zenoh_declare_subscriber(
+            session,
+            key.as_ptr(),
+            on_sample,
+            &count as *const AtomicUsize as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
 
-        zenoh_undeclare_publisher(publisher);
+        let data = b"x";
+        for _ in 0..3 {
+            assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+        }
+        let queued = || {
+            let mut stats = ZenohDispatchStats { queued: 0, high_water: 0, dispatched: 0, dropped: 0 };
+            assert!(matches!(zenoh_session_dispatch_stats(session, &mut stats), ZenohError::Ok));
+            stats.queued
+        };
+        assert!(wait_until(Duration::from_secs(5), || queued() == 3));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        assert_eq!(zenoh_session_pump(session, 2, 0), 2);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        assert_eq!(zenoh_session_pump(session, 0, 0), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+
+        let mut stats = ZenohDispatchStats { queued: 0, high_water: 0, dispatched: 0, dropped: 0 };
+        assert!(matches!(zenoh_session_dispatch_stats(session, &mut stats), ZenohError::Ok));
+        assert_eq!(stats.queued, 0);
+        assert_eq!(stats.high_water, 3);
+        assert_eq!(stats.dispatched, 3);
+        assert_eq!(stats.dropped, 0);
+
+        zenoh_undeclare_subscriber(subscriber);
         zenoh_close(session);
     }
 
     #[test]
-    fn test_subscriber_lifecycle() {
+    fn test_batch_subscriber() {
         let session = zenoh_open(ptr::null());
         assert!(!session.is_null());
 
-        extern "C" fn test_callback(_sample: *const SampleData, _context: *mut c_void) {}
+        extern "C" fn on_batch(samples: *const SampleData, count: usize, context: *mut c_void) {
+            let sizes = unsafe { &*(context as *const Mutex<Vec<usize>>) };
+            let samples = unsafe { std::slice::from_raw_parts(samples, count) };
+            assert!(samples.iter().all(|s| s.payload_len == 1));
+            sizes.lock().unwrap().push(count);
+        }
 
-        let key = CString::new("test/key").unwrap();
+        let sizes: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        let key = CString::new("test/batch").unwrap();
+        let subscriber = zenoh_declare_batch_subscriber(
+            session,
+            key.as_ptr(),
+            3,
+            0,
+            on_batch,
+            &sizes as *const Mutex<Vec<usize>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        let data = b"x";
+        for _ in 0..4 {
+            assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+        }
+        let batcher = &unsafe { &*(subscriber as *const BatchSubscriberWrapper) }.batcher;
+        assert!(wait_until(Duration::from_secs(5), || {
+            batcher.batch.lock().unwrap().samples.len() == 1
+        }));
+        assert_eq!(*sizes.lock().unwrap(), vec![3]);
+
+        assert!(matches!(zenoh_batch_subscriber_flush(subscriber), ZenohError::Ok));
+        assert_eq!(*sizes.lock().unwrap(), vec![3, 1]);
+
+        zenoh_undeclare_batch_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_batch_subscriber_max_delay() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_batch(_samples: *const SampleData, count: usize, context: *mut c_void) {
+            let sizes = unsafe { &*(context as *const Mutex<Vec<usize>>) };
+            sizes.lock().unwrap().push(count);
+        }
+
+        let sizes: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        let key = CString::new("test/batch/delay").unwrap();
+        let subscriber = zenoh_declare_batch_subscriber(
+            session,
+            key.as_ptr(),
+            0,
+            1000,
+            on_batch,
+            &sizes as *const Mutex<Vec<usize>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        // Each put opens a new batch after the timer has gone idle
+        let data = b"x";
+        for round in 1..=2 {
+            assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+            assert!(wait_until(Duration::from_secs(5), || sizes.lock().unwrap().len() == round));
+        }
+        assert_eq!(*sizes.lock().unwrap(), vec![1, 1]);
+
+        zenoh_undeclare_batch_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_batch_subscriber_reentrant_flush() {
+        #[derive(Default)]
+        struct Reentry {
+            subscriber: AtomicUsize,
+            payloads: Mutex<Vec<u8>>,
+            flush_results: Mutex<Vec<bool>>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_batch(samples: *const SampleData, count: usize, context: *mut c_void) {
+            let reentry = unsafe { &*(context as *const Reentry) };
+            let samples = unsafe { std::slice::from_raw_parts(samples, count) };
+            reentry
+                .payloads
+                .lock()
+                .unwrap()
+                .extend(samples.iter().map(|s| unsafe { *s.payload_data }));
+            let subscriber = reentry.subscriber.load(Ordering::SeqCst) as *const c_void;
+            if !subscriber.is_null() {
+                let result = zenoh_batch_subscriber_flush(subscriber);
+                reentry.flush_results.lock().unwrap().push(matches!(result, ZenohError::Ok));
+            }
+        }
+
+        let reentry = Reentry::default();
+        let key = CString::new("test/batch/reenter").unwrap();
+        let subscriber = zenoh_declare_batch_subscriber(
+            session,
+            key.as_ptr(),
+            2,
+            0,
+            on_batch,
+            &reentry as *const Reentry as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+        reentry.subscriber.store(subscriber as usize, Ordering::SeqCst);
+
+        // Flushing from inside the callback neither deadlocks nor reorders samples
+        for i in 1..=5u8 {
+            assert!(matches!(zenoh_put(session, key.as_ptr(), [i].as_ptr(), 1), ZenohError::Ok));
+        }
+        assert!(wait_until(Duration::from_secs(5), || {
+            zenoh_batch_subscriber_flush(subscriber);
+            reentry.payloads.lock().unwrap().len() == 5
+        }));
+        assert_eq!(*reentry.payloads.lock().unwrap(), vec![1, 2, 3, 4, 5]);
+        let flush_results = reentry.flush_results.lock().unwrap();
+        assert!(!flush_results.is_empty());
+        assert!(flush_results.iter().all(|ok| *ok));
+        drop(flush_results);
+
+        zenoh_undeclare_batch_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_view_subscriber() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_view(view: *const SampleView, context: *mut c_void) {
+            let received = unsafe { &*(context as *const Mutex<Vec<(String, Vec<u8>)>>) };
+            let view = unsafe { &*view };
+            let key = unsafe { std::slice::from_raw_parts(view.key_expr, view.key_len) };
+            let slices = unsafe { std::slice::from_raw_parts(view.payload_slices, view.payload_slice_count) };
+            let payload: Vec<u8> = slices
+                .iter()
+                .flat_map(|s| unsafe { std::slice::from_raw_parts(s.data, s.len) }.iter().copied())
+                .collect();
+            assert_eq!(payload.len(), view.payload_len);
+            let encoding = unsafe { std::slice::from_raw_parts(view.encoding, view.encoding_len) };
+            if encoding == b"text/plain;utf-8"
+                && matches!(view.encoding_id, ZenohEncodingId::TextPlain)
+                && view.attachment_data.is_null()
+            {
+                received.lock().unwrap().push((String::from_utf8(key.to_vec()).unwrap(), payload));
+            }
+        }
+
+        let received: Mutex<Vec<(String, Vec<u8>)>> = Mutex::new(Vec::new());
+        let key = CString::new("test/view").unwrap();
+        let subscriber = zenoh_declare_subscriber_view(
+            session,
+            key.as_ptr(),
+            on_view,
+            &received as *const Mutex<Vec<(String, Vec<u8>)>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        let data = b"hello";
+        let encoding = CString::new("text/plain;utf-8").unwrap();
+        assert!(matches!(
+            zenoh_put_with_encoding_str(session, key.as_ptr(), data.as_ptr(), data.len(), encoding.as_ptr()),
+            ZenohError::Ok
+        ));
+        assert!(wait_until(Duration::from_secs(5), || !received.lock().unwrap().is_empty()));
+        assert_eq!(*received.lock().unwrap(), vec![("test/view".to_string(), data.to_vec())]);
+
+        zenoh_undeclare_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_view_delivery_does_not_allocate() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        // A pull subscriber hands the samples to this thread, where the allocations
+        // of the view delivery path can be counted
+        let key = CString::new("test/view/alloc").unwrap();
+        let subscriber = zenoh_declare_pull_subscriber(session, key.as_ptr(), ZenohChannelKind::Fifo, 0);
+        assert!(!subscriber.is_null());
+
+        let data = b"payload";
+        for _ in 0..2 {
+            assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+        }
+        let pull = unsafe { &*(subscriber as *const PullSubscriberWrapper) };
+        let mut samples = Vec::new();
+        assert!(wait_until(Duration::from_secs(5), || {
+            let mut receiver = pull.receiver().unwrap();
+            while let Ok(Some(sample)) = receiver.try_recv() {
+                samples.push(sample);
+            }
+            samples.len() == 2
+        }));
+
+        extern "C" fn on_view(view: *const SampleView, context: *mut c_void) {
+            let total = unsafe { &*(context as *const AtomicUsize) };
+            total.fetch_add(unsafe { (*view).payload_len }, Ordering::SeqCst);
+        }
+
+        let total = AtomicUsize::new(0);
+        let context = &total as *const AtomicUsize as *mut c_void;
+        // The first delivery sizes this thread's scratch buffers
+        deliver_sample_view(&samples[0], on_view, context);
+        let before = ALLOCATIONS.with(Cell::get);
+        deliver_sample_view(&samples[1], on_view, context);
+        assert_eq!(ALLOCATIONS.with(Cell::get), before);
+        assert_eq!(total.load(Ordering::SeqCst), 2 * data.len());
+
+        drop(samples);
+        zenoh_undeclare_pull_subscriber(subscriber);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_sample_clone_outlives_callback() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_sample(sample: *const SampleData, context: *mut c_void) {
+            let retained = unsafe { &*(context as *const Mutex<Vec<usize>>) };
+            let handle = zenoh_sample_clone(unsafe { (*sample).sample });
+            assert!(!handle.is_null());
+            retained.lock().unwrap().push(handle as usize);
+        }
+
+        let retained: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        let key = CString::new("test/retain").unwrap();
         let subscriber = zenoh_declare_subscriber(
             session,
             key.as_ptr(),
-            test_callback,
-            ptr::null_mut(),
+            on_sample,
+            &retained as *const Mutex<Vec<usize>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        let data = b"payload";
+        assert!(matches!(zenoh_put(session, key.as_ptr(), data.as_ptr(), data.len()), ZenohError::Ok));
+        assert!(wait_until(Duration::from_secs(5), || !retained.lock().unwrap().is_empty()));
+        zenoh_undeclare_subscriber(subscriber);
+
+        let handles = retained.lock().unwrap().clone();
+        assert_eq!(handles.len(), 1);
+        let handle = handles[0] as *const ZenohOwnedSample;
+
+        let mut len = 0;
+        let key_ptr = zenoh_sample_key_expr(handle, &mut len);
+        assert_eq!(unsafe { std::slice::from_raw_parts(key_ptr, len) }, b"test/retain");
+        assert_eq!(zenoh_sample_payload_len(handle), data.len());
+        let count = zenoh_sample_payload_slices(handle, ptr::null_mut(), 0);
+        assert_eq!(count, zenoh_sample_payload_slice_count(handle));
+        let mut slices = vec![ZenohPayloadSlice { data: ptr::null(), len: 0 }; count];
+        assert_eq!(zenoh_sample_payload_slices(handle, slices.as_mut_ptr(), slices.len()), count);
+        let payload: Vec<u8> = slices
+            .iter()
+            .flat_map(|s| unsafe { std::slice::from_raw_parts(s.data, s.len) }.iter().copied())
+            .collect();
+        assert_eq!(payload, data);
+        assert!(matches!(zenoh_sample_kind(handle), ZenohSampleKind::Put));
+
+        let second = zenoh_sample_retain(handle);
+        zenoh_sample_drop(handle);
+        assert_eq!(zenoh_sample_payload_len(second), data.len());
+        zenoh_sample_drop(second);
+
+        assert_eq!(zenoh_sample_payload_len(ptr::null()), 0);
+        assert!(!zenoh_last_error().is_null());
+
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_view_sample_clone_outlives_callback() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_view(view: *const SampleView, context: *mut c_void) {
+            let retained = unsafe { &*(context as *const Mutex<Vec<usize>>) };
+            let handle = zenoh_sample_clone(unsafe { (*view).sample });
+            assert!(!handle.is_null());
+            retained.lock().unwrap().push(handle as usize);
+        }
+
+        let retained: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+        let key = CString::new("test/retain/view").unwrap();
+        let subscriber = zenoh_declare_subscriber_view(
+            session,
+            key.as_ptr(),
+            on_view,
+            &retained as *const Mutex<Vec<usize>> as *mut c_void,
+        );
+        assert!(!subscriber.is_null());
+
+        let data = b"payload";
+        let att_key = CString::new("trace-id").unwrap();
+        let att_value = b"42";
+        let items = [ZenohAttachmentItem {
+            key: att_key.as_ptr(),
+            value: att_value.as_ptr(),
+            value_len: att_value.len(),
+        }];
+        let mut options = zenoh_put_options_default();
+        options.attachment_items = items.as_ptr();
+        options.attachment_count = items.len();
+        assert!(matches!(
+            zenoh_put_with_options(session, key.as_ptr(), data.as_ptr(), data.len(), &options),
+            ZenohError::Ok
+        ));
+        assert!(wait_until(Duration::from_secs(5), || !retained.lock().unwrap().is_empty()));
+        zenoh_undeclare_subscriber(subscriber);
+
+        let handle = retained.lock().unwrap()[0] as *const ZenohOwnedSample;
+        let mut len = 0;
+        let key_ptr = zenoh_sample_key_expr(handle, &mut len);
+        assert_eq!(unsafe { std::slice::from_raw_parts(key_ptr, len) }, b"test/retain/view");
+        assert_eq!(zenoh_sample_payload_len(handle), data.len());
+        assert!(!zenoh_sample_attachment(handle, &mut len).is_null());
+        assert!(len > 0);
+        zenoh_sample_drop(handle);
+
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_presence_tracker_snapshot() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        let token_key = CString::new("test/presence/player1").unwrap();
+        let token = zenoh_liveliness_declare_token(session, token_key.as_ptr());
+        assert!(!token.is_null());
+
+        extern "C" fn on_change(_key: *const c_char, _is_alive: bool, _context: *mut c_void) {}
+
+        let key = CString::new("test/presence/*").unwrap();
+        let tracker = zenoh_presence_declare(session, key.as_ptr(), on_change, ptr::null_mut(), 1000);
+        assert!(!tracker.is_null());
+        assert_eq!(zenoh_presence_count(tracker), 1);
+        assert!(zenoh_presence_contains(tracker, token_key.as_ptr()));
+
+        zenoh_presence_undeclare(tracker);
+        zenoh_liveliness_undeclare_token(token);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_presence_callback_reenters_tracker() {
+        #[derive(Default)]
+        struct Reentry {
+            tracker: AtomicUsize,
+            /// (key, is_alive, count, contains, snapshot) observed from inside the callback
+            seen: Mutex<Vec<(String, bool, usize, bool, Vec<String>)>>,
+        }
+
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn collect_key(key: *const c_char, context: *mut c_void) {
+            let keys = unsafe { &mut *(context as *mut Vec<String>) };
+            keys.push(unsafe { CStr::from_ptr(key) }.to_string_lossy().into_owned());
+        }
+        extern "C" fn on_change(key: *const c_char, is_alive: bool, context: *mut c_void) {
+            let reentry = unsafe { &*(context as *const Reentry) };
+            let tracker = reentry.tracker.load(Ordering::SeqCst) as *const c_void;
+            if tracker.is_null() {
+                return;
+            }
+            let count = zenoh_presence_count(tracker);
+            let contains = zenoh_presence_contains(tracker, key);
+            let mut snapshot: Vec<String> = Vec::new();
+            zenoh_presence_snapshot(tracker, collect_key, &mut snapshot as *mut Vec<String> as *mut c_void);
+            let key = unsafe { CStr::from_ptr(key) }.to_string_lossy().into_owned();
+            reentry.seen.lock().unwrap().push((key, is_alive, count, contains, snapshot));
+        }
+
+        let reentry = Reentry::default();
+        let key = CString::new("test/reenter/*").unwrap();
+        let tracker = zenoh_presence_declare(
+            session,
+            key.as_ptr(),
+            on_change,
+            &reentry as *const Reentry as *mut c_void,
+            1000,
+        );
+        assert!(!tracker.is_null());
+        reentry.tracker.store(tracker as usize, Ordering::SeqCst);
+
+        let token_key = CString::new("test/reenter/p1").unwrap();
+        let token = zenoh_liveliness_declare_token(session, token_key.as_ptr());
+        assert!(!token.is_null());
+        assert!(wait_until(Duration::from_secs(5), || !reentry.seen.lock().unwrap().is_empty()));
+
+        zenoh_liveliness_undeclare_token(token);
+        assert!(wait_until(Duration::from_secs(5), || reentry.seen.lock().unwrap().len() == 2));
+
+        let p1 = "test/reenter/p1".to_string();
+        assert_eq!(
+            *reentry.seen.lock().unwrap(),
+            [
+                (p1.clone(), true, 1, true, vec![p1.clone()]),
+                (p1.clone(), false, 0, false, vec![]),
+            ]
+        );
+
+        zenoh_presence_undeclare(tracker);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_presence_events_follow_state_order() {
+        extern "C" fn record(key: *const c_char, is_alive: bool, context: *mut c_void) {
+            let events = unsafe { &*(context as *const Mutex<Vec<(String, bool)>>) };
+            let key = unsafe { CStr::from_ptr(key) }.to_string_lossy().into_owned();
+            events.lock().unwrap().push((key, is_alive));
+        }
+
+        let queue = Arc::new(DispatchQueue::new());
+        for _ in 0..200 {
+            let events: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+            let (dispatcher, _guard) = queue.dispatcher();
+            let tracker = PresenceTracker::new(dispatcher, record, &events as *const _ as usize);
+
+            // A stale initial reply for "x" races with a join and a leave from the subscriber
+            let barrier = std::sync::Barrier::new(2);
+            std::thread::scope(|scope| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    tracker.on_initial("x");
+                });
+                scope.spawn(|| {
+                    barrier.wait();
+                    tracker.on_change("x", true);
+                    tracker.on_change("x", false);
+                });
+            });
+            tracker.finish_sync();
+
+            // Events alternate starting with a join, and the last one matches the state
+            let events = events.into_inner().unwrap();
+            for (i, (key, is_alive)) in events.iter().enumerate() {
+                assert_eq!(key, "x");
+                assert_eq!(*is_alive, i % 2 == 0, "events out of order: {:?}", events);
+            }
+            let alive = tracker.state.lock().unwrap().alive.contains("x");
+            assert_eq!(events.last().map(|(_, is_alive)| *is_alive).unwrap_or(false), alive);
+        }
+    }
+
+    #[test]
+    fn test_keyexpr_utilities() {
+        let valid = CString::new("game/player/*/position").unwrap();
+        let invalid = CString::new("game//player").unwrap();
+        assert!(matches!(zenoh_keyexpr_validate(valid.as_ptr()), ZenohError::Ok));
+        assert!(matches!(zenoh_keyexpr_validate(invalid.as_ptr()), ZenohError::InvalidKeyExpr));
+        assert!(!zenoh_last_error().is_null());
+
+        let wild = CString::new("game/**").unwrap();
+        let mut out = false;
+        assert!(matches!(zenoh_keyexpr_includes(wild.as_ptr(), valid.as_ptr(), &mut out), ZenohError::Ok));
+        assert!(out);
+        assert!(matches!(zenoh_keyexpr_includes(valid.as_ptr(), wild.as_ptr(), &mut out), ZenohError::Ok));
+        assert!(!out);
+        assert!(matches!(zenoh_keyexpr_intersects(valid.as_ptr(), wild.as_ptr(), &mut out), ZenohError::Ok));
+        assert!(out);
+
+        let non_canon = CString::new("a/**/**/b").unwrap();
+        let canon = zenoh_keyexpr_canonize(non_canon.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(canon) }.to_str().unwrap(), "a/**/b");
+        zenoh_free_string(canon);
+
+        let base = CString::new("game/player").unwrap();
+        let suffix = CString::new("42").unwrap();
+        let joined = zenoh_keyexpr_join(base.as_ptr(), suffix.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(joined) }.to_str().unwrap(), "game/player/42");
+        zenoh_free_string(joined);
+        let concat = zenoh_keyexpr_concat(base.as_ptr(), suffix.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(concat) }.to_str().unwrap(), "game/player42");
+        zenoh_free_string(concat);
+    }
+
+    #[test]
+    fn test_declared_keyexpr_put_subscribe() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        extern "C" fn on_sample(sample: *const SampleData, context: *mut c_void) {
+            let received = unsafe { &*(context as *const Mutex<Vec<(String, Vec<u8>)>>) };
+            let sample = unsafe { &*sample };
+            let key = unsafe { CStr::from_ptr(sample.key_expr) }.to_string_lossy().into_owned();
+            let payload = unsafe { std::slice::from_raw_parts(sample.payload_data, sample.payload_len) };
+            received.lock().unwrap().push((key, payload.to_vec()));
+        }
+
+        let key = CString::new("test/declared/ke").unwrap();
+        let keyexpr = zenoh_declare_keyexpr(session, key.as_ptr());
+        assert!(!keyexpr.is_null());
+
+        let received: Mutex<Vec<(String, Vec<u8>)>> = Mutex::new(Vec::new());
+        let subscriber = zenoh_declare_subscriber_keyexpr(
+            session,
+            keyexpr,
+            on_sample,
+            &received as *const Mutex<Vec<(String, Vec<u8>)>> as *mut c_void,
         );
         assert!(!subscriber.is_null());
 
+        let data = b"via-id";
+        let result = zenoh_put_keyexpr(session, keyexpr, data.as_ptr(), data.len(), ptr::null());
+        assert!(matches!(result, ZenohError::Ok));
+        assert!(wait_until(Duration::from_secs(5), || !received.lock().unwrap().is_empty()));
+        assert_eq!(*received.lock().unwrap(), [("test/declared/ke".to_string(), data.to_vec())]);
+
+        // A handle declared on one session is rejected by another
+        let other = zenoh_open(ptr::null());
+        assert!(!other.is_null());
+        let result = zenoh_put_keyexpr(other, keyexpr, data.as_ptr(), data.len(), ptr::null());
+        assert!(matches!(result, ZenohError::InvalidKeyExpr));
+        assert!(zenoh_declare_publisher_keyexpr(other, keyexpr, ptr::null()).is_null());
+        zenoh_close(other);
+
         zenoh_undeclare_subscriber(subscriber);
+        zenoh_undeclare_keyexpr(keyexpr);
+        zenoh_close(session);
+    }
+
+    #[test]
+    fn test_keformat_roundtrip() {
+        let spec = CString::new("game/player/${id:*}/position").unwrap();
+        let format = zenoh_keformat_new(spec.as_ptr());
+        assert!(!format.is_null());
+
+        let id = CString::new("id").unwrap();
+        let value = CString::new("42").unwrap();
+        let values = [ZenohKeFormatValue { id: id.as_ptr(), value: value.as_ptr() }];
+        let key = zenoh_keformat_format(format, values.as_ptr(), values.len());
+        assert_eq!(unsafe { CStr::from_ptr(key) }.to_str().unwrap(), "game/player/42/position");
+
+        let parsed = zenoh_keformat_parse_value(format, key, id.as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(parsed) }.to_str().unwrap(), "42");
+        zenoh_free_string(parsed);
+        zenoh_free_string(key);
+
+        let bad_value = CString::new("a/b").unwrap();
+        let values = [ZenohKeFormatValue { id: id.as_ptr(), value: bad_value.as_ptr() }];
+        assert!(zenoh_keformat_format(format, values.as_ptr(), values.len()).is_null());
+
+        let other = CString::new("game/npc/42/position").unwrap();
+        assert!(zenoh_keformat_parse_value(format, other.as_ptr(), id.as_ptr()).is_null());
+
+        zenoh_keformat_free(format);
+    }
+
+    #[test]
+    fn test_selector_build_and_parse() {
+        let key = CString::new("sensors/**").unwrap();
+        let names = [CString::new("_time").unwrap(), CString::new("unit").unwrap()];
+        let values = [CString::new("[now(-1h)..]").unwrap(), CString::new("C").unwrap()];
+        let params = [
+            ZenohSelectorParameter { name: names[0].as_ptr(), value: values[0].as_ptr() },
+            ZenohSelectorParameter { name: names[1].as_ptr(), value: values[1].as_ptr() },
+        ];
+        let selector = zenoh_selector_build(key.as_ptr(), params.as_ptr(), params.len());
+        assert!(!selector.is_null());
+
+        let key_part = zenoh_selector_key_expr(selector);
+        assert_eq!(unsafe { CStr::from_ptr(key_part) }.to_str().unwrap(), "sensors/**");
+        zenoh_free_string(key_part);
+        let unit = zenoh_selector_parameter(selector, names[1].as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(unit) }.to_str().unwrap(), "C");
+        zenoh_free_string(unit);
+
+        let mut present = false;
+        let unbounded = ZenohTimeBound { kind: ZenohTimeBoundKind::Unbounded, time_ntp64: 0 };
+        let (mut start, mut end) = (unbounded, unbounded);
+        assert!(matches!(
+            zenoh_selector_time_range(selector, &mut present, &mut start, &mut end),
+            ZenohError::Ok
+        ));
+        assert!(present);
+        assert!(matches!(start.kind, ZenohTimeBoundKind::Inclusive));
+        assert!(start.time_ntp64 > 0);
+        assert!(matches!(end.kind, ZenohTimeBoundKind::Unbounded));
+        zenoh_free_string(selector);
+
+        let empty_name = CString::new("").unwrap();
+        let params = [ZenohSelectorParameter { name: empty_name.as_ptr(), value: values[1].as_ptr() }];
+        assert!(zenoh_selector_build(key.as_ptr(), params.as_ptr(), params.len()).is_null());
+    }
+
+    #[test]
+    fn test_selector_escaping_round_trips() {
+        extern "C" fn collect(name: *const c_char, value: *const c_char, context: *mut c_void) {
+            let pairs = unsafe { &mut *(context as *mut Vec<(String, String)>) };
+            let name = unsafe { CStr::from_ptr(name) }.to_str().unwrap().to_string();
+            let value = unsafe { CStr::from_ptr(value) }.to_str().unwrap().to_string();
+            pairs.push((name, value));
+        }
+
+        let key = CString::new("sensors/**").unwrap();
+        let pairs = [("a;b=c", "x=1;y&z"), ("pct", "100%25 ü"), ("plain", "v")];
+        let names: Vec<CString> = pairs.iter().map(|(n, _)| CString::new(*n).unwrap()).collect();
+        let values: Vec<CString> = pairs.iter().map(|(_, v)| CString::new(*v).unwrap()).collect();
+        let params: Vec<ZenohSelectorParameter> = names
+            .iter()
+            .zip(&values)
+            .map(|(name, value)| ZenohSelectorParameter { name: name.as_ptr(), value: value.as_ptr() })
+            .collect();
+        let selector = zenoh_selector_build(key.as_ptr(), params.as_ptr(), params.len());
+        assert!(!selector.is_null());
+        assert_eq!(
+            unsafe { CStr::from_ptr(selector) }.to_str().unwrap(),
+            "sensors/**?a%3Bb%3Dc=x%3D1%3By%26z;pct=100%2525 ü;plain=v"
+        );
+
+        let mut parsed: Vec<(String, String)> = Vec::new();
+        assert!(matches!(
+            zenoh_selector_parameters(selector, collect, &mut parsed as *mut _ as *mut c_void),
+            ZenohError::Ok
+        ));
+        let expected: Vec<(String, String)> =
+            pairs.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect();
+        assert_eq!(parsed, expected);
+
+        let value = zenoh_selector_parameter(selector, names[0].as_ptr());
+        assert_eq!(unsafe { CStr::from_ptr(value) }.to_str().unwrap(), "x=1;y&z");
+        zenoh_free_string(value);
+        zenoh_free_string(selector);
+
+        assert_eq!(unescape_parameter("50%"), "50%");
+        assert_eq!(unescape_parameter("%zz%4"), "%zz%4");
+        assert_eq!(unescape_parameter("%FF"), "%FF");
+    }
+
+    #[test]
+    fn test_put_with_options() {
+        let session = zenoh_open(ptr::null());
+        assert!(!session.is_null());
+
+        let key = CString::new("test/key").unwrap();
+        let att_key = CString::new("trace-id").unwrap();
+        let att_value = b"42";
+        let items = [ZenohAttachmentItem {
+            key: att_key.as_ptr(),
+            value: att_value.as_ptr(),
+            value_len: att_value.len(),
+        }];
+
+        let mut options = zenoh_put_options_default();
+        options.encoding_id = ZenohEncodingId::AppJson;
+        options.attachment_items = items.as_ptr();
+        options.attachment_count = items.len();
+        options.priority = ZenohPriority::RealTime;
+
+        let data = b"{}";
+        let result = zenoh_put_with_options(session, key.as_ptr(), data.as_ptr(), data.len(), &options);
+        assert!(matches!(result, ZenohError::Ok));
+
+        let publisher = zenoh_declare_publisher(session, key.as_ptr());
+        assert!(!publisher.is_null());
+        let mut pub_options = zenoh_publisher_put_options_default();
+        pub_options.encoding_id = ZenohEncodingId::AppJson;
+        pub_options.attachment_items = items.as_ptr();
+        pub_options.attachment_count = items.len();
+        let result = zenoh_publisher_put_with_options(publisher, data.as_ptr(), data.len(), &pub_options);
+        assert!(matches!(result, ZenohError::Ok));
+        zenoh_undeclare_publisher(publisher);
+
         zenoh_close(session);
     }
 }
@@ -70,6 +70,10 @@ public class ZenohQueryableExample : MonoBehaviour
         {
             Debug.LogError($"[Zenoh] Failed to reply: {ex.Message}");
         }
+        finally
+        {
+            query.Dispose();
+        }
     }
 
     void OnDestroy()
@@ -0,0 +1,62 @@
+using System;
+using System.Collections.Generic;
+using System.Threading.Tasks;
+
+namespace ZenohDotNet.Client;
+
+/// <summary>
+/// High-level async Zenoh subscriber that delivers samples in batches.
+/// </summary>
+public sealed class BatchSubscriber : IAsyncDisposable
+{
+    private readonly Native.BatchSubscriber _nativeSubscriber;
+    private bool _disposed;
+
+    /// <summary>
+    /// Gets the key expression this subscriber is listening on.
+    /// </summary>
+    public string KeyExpression => _nativeSubscriber.KeyExpression;
+
+    internal BatchSubscriber(Native.Session session, string keyExpr, int maxSamples, TimeSpan maxDelay, Action<IReadOnlyList<Sample>> callback)
+    {
+        // Wrap the callback to convert Native.Sample to Client.Sample
+        _nativeSubscriber = session.DeclareBatchSubscriber(keyExpr, maxSamples, maxDelay, nativeSamples =>
+        {
+            var clientSamples = new Sample[nativeSamples.Count];
+            for (int i = 0; i < clientSamples.Length; i++)
+            {
+                clientSamples[i] = Session.ConvertNativeSample(nativeSamples[i]);
+            }
+            callback(clientSamples);
+        });
+    }
+
+    /// <summary>
+    /// Delivers the samples accumulated so far immediately, without waiting for either batch bound.
+    /// May be called from the batch callback; the batch is then delivered right after the callback returns.
+    /// </summary>
+    /// <exception cref="Native.ZenohException">Thrown when the flush fails.</exception>
+    public void Flush()
+    {
+        ThrowIfDisposed();
+        _nativeSubscriber.Flush();
+    }
+
+    private void ThrowIfDisposed()
+    {
+        ObjectDisposedException.ThrowIf(_disposed, this);
+    }
+
+    /// <summary>
+    /// Asynchronously disposes the subscriber and releases all resources.
+    /// Samples not yet delivered are discarded; call <see cref="Flush"/> first to receive them.
+    /// </summary>
+    public async ValueTask DisposeAsync()
+    {
+        if (!_disposed)
+        {
+            await Task.Run(() => _nativeSubscriber.Dispose()).ConfigureAwait(false);
+            _disposed = true;
+        }
+    }
+}
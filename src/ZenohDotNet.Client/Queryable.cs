@@ -39,8 +39,11 @@ public sealed class Queryable : IAsyncDisposable
 
 /// <summary>
 /// Represents a query received by a queryable.
+/// A query may be replied to several times, also after the queryable callback has
+/// returned. It is finalized when <see cref="Dispose"/> is called, or when it is
+/// garbage collected if it never was; dispose it after the last reply.
 /// </summary>
-public sealed class Query
+public sealed class Query : IDisposable
 {
     private readonly Native.Query _nativeQuery;
 
@@ -73,4 +76,12 @@ public sealed class Query
     {
         _nativeQuery.Reply(keyExpr, value);
     }
+
+    /// <summary>
+    /// Finalizes the query, signalling the requester that no more replies will follow.
+    /// </summary>
+    public void Dispose()
+    {
+        _nativeQuery.Dispose();
+    }
 }
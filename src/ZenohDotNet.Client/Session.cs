@@ -103,6 +103,28 @@ public sealed class Session : IAsyncDisposable
         return await Task.Run(() => new Subscriber(_nativeSession, keyExpr, callback), cancellationToken).ConfigureAwait(false);
     }
 
+    /// <summary>
+    /// Declares a subscriber that delivers samples in batches.
+    /// A batch is delivered once it holds <paramref name="maxSamples"/> samples, or
+    /// <paramref name="maxDelay"/> after its first sample arrived, whichever comes first.
+    /// </summary>
+    /// <param name="keyExpr">The key expression to subscribe to.</param>
+    /// <param name="maxSamples">Maximum number of samples per batch, or 0 for no limit.</param>
+    /// <param name="maxDelay">Maximum time a sample waits for its batch, or TimeSpan.Zero for no limit. Delays below 1 ms behave as roughly 1 ms.</param>
+    /// <param name="callback">The callback to invoke with each batch, in arrival order.</param>
+    /// <param name="cancellationToken">Cancellation token.</param>
+    /// <returns>A task representing the async operation, containing the BatchSubscriber.</returns>
+    /// <exception cref="ArgumentNullException">Thrown when keyExpr or callback is null.</exception>
+    /// <exception cref="Native.ZenohException">Thrown when the subscriber cannot be created, including when both bounds are disabled.</exception>
+    public async Task<BatchSubscriber> DeclareBatchSubscriberAsync(string keyExpr, int maxSamples, TimeSpan maxDelay, Action<IReadOnlyList<Sample>> callback, CancellationToken cancellationToken = default)
+    {
+        ArgumentNullException.ThrowIfNull(keyExpr);
+        ArgumentNullException.ThrowIfNull(callback);
+        ThrowIfDisposed();
+
+        return await Task.Run(() => new BatchSubscriber(_nativeSession, keyExpr, maxSamples, maxDelay, callback), cancellationToken).ConfigureAwait(false);
+    }
+
     /// <summary>
     /// Declares a queryable for the specified key expression.
     /// </summary>
@@ -0,0 +1,135 @@
+using System;
+using System.Collections.Generic;
+using System.Runtime.InteropServices;
+using System.Text;
+using ZenohDotNet.Native.FFI;
+
+namespace ZenohDotNet.Native
+{
+    /// <summary>
+    /// Represents a Zenoh subscriber that delivers samples in batches, cutting the number
+    /// of callback invocations on high-rate key expressions.
+    /// </summary>
+    public class BatchSubscriber : IDisposable
+    {
+        private unsafe void* _handle;
+        private readonly Session _session;
+        private readonly string _keyExpr;
+        private readonly Action<IReadOnlyList<Sample>> _callback;
+        private GCHandle _callbackHandle;
+        private NativeMethods.zenoh_declare_batch_subscriber_callback_delegate? _nativeCallback;
+        private bool _disposed;
+
+        /// <summary>
+        /// Gets the key expression this subscriber is listening on.
+        /// </summary>
+        public string KeyExpression => _keyExpr;
+
+        internal unsafe BatchSubscriber(Session session, string keyExpr, int maxSamples, TimeSpan maxDelay, Action<IReadOnlyList<Sample>> callback)
+        {
+            _session = session ?? throw new ArgumentNullException(nameof(session));
+            _keyExpr = keyExpr ?? throw new ArgumentNullException(nameof(keyExpr));
+            _callback = callback ?? throw new ArgumentNullException(nameof(callback));
+            if (maxSamples < 0)
+                throw new ArgumentOutOfRangeException(nameof(maxSamples));
+            if (maxDelay < TimeSpan.Zero)
+                throw new ArgumentOutOfRangeException(nameof(maxDelay));
+
+            var keyBytes = Encoding.UTF8.GetBytes(keyExpr + "\0");
+            var maxDelayUs = (ulong)(maxDelay.Ticks / (TimeSpan.TicksPerMillisecond / 1000));
+
+            // Create native callback and prevent GC
+            _nativeCallback = OnBatchReceived;
+            _callbackHandle = GCHandle.Alloc(_nativeCallback);
+
+            fixed (byte* keyPtr = keyBytes)
+            {
+                _handle = NativeMethods.zenoh_declare_batch_subscriber(
+                    session.Handle,
+                    keyPtr,
+                    (nuint)maxSamples,
+                    maxDelayUs,
+                    _nativeCallback,
+                    null);
+            }
+
+            if (_handle == null)
+            {
+                _callbackHandle.Free();
+                throw ZenohException.FromLastError($"Failed to declare batch subscriber for key expression: {keyExpr}");
+            }
+        }
+
+        /// <summary>
+        /// Delivers the samples accumulated so far immediately, without waiting for either batch bound.
+        /// May be called from the batch callback; the batch is then delivered right after the callback returns.
+        /// </summary>
+        public unsafe void Flush()
+        {
+            ThrowIfDisposed();
+
+            var result = NativeMethods.zenoh_batch_subscriber_flush(_handle);
+            if (result != ZenohError.Ok)
+            {
+                throw ZenohException.FromLastError($"Failed to flush batch subscriber: {result}");
+            }
+        }
+
+        private unsafe void OnBatchReceived(SampleData* samplesPtr, nuint count, void* contextPtr)
+        {
+            try
+            {
+                if (samplesPtr == null)
+                    return;
+
+                var samples = new Sample[(int)count];
+                for (int i = 0; i < samples.Length; i++)
+                {
+                    samples[i] = Sample.FromNative(samplesPtr + i);
+                }
+
+                _callback?.Invoke(samples);
+            }
+            catch (Exception ex)
+            {
+                Console.Error.WriteLine($"Exception in batch subscriber callback: {ex}");
+            }
+        }
+
+        private void ThrowIfDisposed()
+        {
+            if (_disposed)
+                throw new ObjectDisposedException(nameof(BatchSubscriber));
+        }
+
+        ~BatchSubscriber()
+        {
+            Dispose(false);
+        }
+
+        public void Dispose()
+        {
+            Dispose(true);
+            GC.SuppressFinalize(this);
+        }
+
+        protected virtual unsafe void Dispose(bool disposing)
+        {
+            if (!_disposed)
+            {
+                if (_handle != null)
+                {
+                    NativeMethods.zenoh_undeclare_batch_subscriber(_handle);
+                    _handle = null;
+                }
+
+                if (_callbackHandle.IsAllocated)
+                {
+                    _callbackHandle.Free();
+                }
+
+                _disposed = true;
+            }
+        }
+    }
+}
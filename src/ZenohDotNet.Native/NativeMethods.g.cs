@@ -21,21 +21,102 @@ namespace ZenohDotNet.Native.FFI
 
 
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_declare_batch_subscriber_callback_delegate(SampleData* arg1, nuint arg2, void* arg3);
+
         [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
         internal delegate void zenoh_declare_queryable_callback_delegate(void* arg1, void* arg2);
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_declare_queryable_with_options_callback_delegate(void* arg1, void* arg2);
+
         [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
         internal delegate void zenoh_declare_subscriber_callback_delegate(SampleData* arg1, void* arg2);
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_declare_subscriber_keyexpr_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_declare_subscriber_view_callback_delegate(SampleView* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_async_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_async_done_callback_delegate(ZenohError arg1, byte* arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_async_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
         [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
         internal delegate void zenoh_get_callback_delegate(SampleData* arg1, void* arg2);
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_keyexpr_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_keyexpr_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_with_error_callback_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_with_error_callback_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_with_options_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_get_with_options_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_keformat_parse_callback_delegate(byte* arg1, byte* arg2, void* arg3);
+
         [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
         internal delegate void zenoh_liveliness_declare_subscriber_callback_delegate(byte* arg1, bool arg2, void* arg3);
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_liveliness_declare_subscriber_with_options_callback_delegate(byte* arg1, bool arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_liveliness_get_async_callback_delegate(byte* arg1, bool arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_liveliness_get_async_done_callback_delegate(ZenohError arg1, byte* arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_liveliness_get_callback_delegate(byte* arg1, bool arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_presence_declare_callback_delegate(byte* arg1, bool arg2, void* arg3);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_presence_snapshot_callback_delegate(byte* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_publisher_declare_matching_listener_callback_delegate(bool arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_querier_declare_matching_listener_callback_delegate(bool arg1, void* arg2);
+
         [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
         internal delegate void zenoh_querier_get_callback_delegate(SampleData* arg1, void* arg2);
 
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_querier_get_with_error_callback_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_querier_get_with_error_callback_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_querier_get_with_options_callback_delegate(SampleData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_querier_get_with_options_error_callback_delegate(ReplyErrorData* arg1, void* arg2);
+
+        [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+        internal delegate void zenoh_selector_parameters_callback_delegate(byte* arg1, byte* arg2, void* arg3);
+
 
 
         /// <summary>
@@ -86,6 +167,12 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Declares a subscriber on the given key expression with a callback.
         ///  Returns a pointer on success, NULL on failure.
+        ///
+        ///  # Safety
+        ///  The SampleData pointer passed to the callback is valid only during the callback invocation.
+        ///  Do not store this pointer or its contents (key_expr, payload_data) for later use.
+        ///  Copy the data if you need to retain it.
+        ///
         ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_declare_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
@@ -106,11 +193,78 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Performs a get query (request-response pattern).
         ///  Returns 0 on success, error code on failure.
+        ///  Error replies are discarded; use zenoh_get_with_error_callback to receive them.
+        ///
+        ///  # Safety
+        ///  The SampleData pointer passed to the callback is valid only during the callback invocation.
+        ///  Do not store this pointer or its contents (key_expr, payload_data) for later use.
+        ///  Copy the data if you need to retain it.
+        ///
         ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_get", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ZenohError zenoh_get(void* session, byte* selector, zenoh_get_callback_delegate callback, void* context);
 
+        /// <summary>
+        ///  Performs a get query, delivering error replies from queryables to `error_callback`.
+        ///  Returns 0 on success, error code on failure.
+        ///
+        ///  # Safety
+        ///  The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+        ///  during the callback invocation.
+        ///  Do not store these pointers or their contents for later use.
+        ///  Copy the data if you need to retain it.
+        ///
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_get_with_error_callback", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_get_with_error_callback(void* session, byte* selector, zenoh_get_with_error_callback_callback_delegate callback, zenoh_get_with_error_callback_error_callback_delegate error_callback, void* context);
+
+        /// <summary>
+        ///  Creates default get options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_get_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern GetOptions zenoh_get_options_default();
+
+        /// <summary>
+        ///  Performs a get query with target, consolidation, timeout, payload,
+        ///  encoding, attachment and destination options.
+        ///  Pass NULL options for defaults.
+        ///  Blocks until all replies have been received or the query times out.
+        ///  Error replies from queryables are delivered to `error_callback`.
+        ///
+        ///  # Safety
+        ///  The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+        ///  during the callback invocation.
+        ///  Do not store these pointers or their contents for later use.
+        ///  Copy the data if you need to retain it.
+        ///
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_get_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_get_with_options(void* session, byte* selector, GetOptions* options, zenoh_get_with_options_callback_delegate callback, zenoh_get_with_options_error_callback_delegate error_callback, void* context);
+
+        /// <summary>
+        ///  Performs a get query without blocking the calling thread.
+        ///  Returns as soon as the query has been scheduled; replies are delivered to
+        ///  `callback` (or `error_callback` for error replies) on a runtime thread,
+        ///  then `done_callback` is invoked exactly once
+        ///  when the reply channel closes (all replies received or timeout).
+        ///  Pass NULL options for defaults.
+        ///
+        ///  # Safety
+        ///  The SampleData and ReplyErrorData pointers passed to the callbacks are valid only
+        ///  during the callback invocation.
+        ///  `context` must remain valid until `done_callback` has been invoked, or until the
+        ///  session is closed: callbacks still queued in the dispatch queue at that point,
+        ///  including `done_callback`, are discarded.
+        ///
+        ///  Returns a non-Ok code (and never invokes the callbacks) if the arguments are invalid.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_get_async", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_get_async(void* session, byte* selector, GetOptions* options, zenoh_get_async_callback_delegate callback, zenoh_get_async_error_callback_delegate error_callback, zenoh_get_async_done_callback_delegate done_callback, void* context);
+
         /// <summary>
         ///  Declares a queryable that responds to get queries.
         ///  Returns a pointer on success, NULL on failure.
@@ -119,21 +273,142 @@ namespace ZenohDotNet.Native.FFI
         [DllImport(__DllName, EntryPoint = "zenoh_declare_queryable", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void* zenoh_declare_queryable(void* session, byte* key_expr, zenoh_declare_queryable_callback_delegate callback, void* context);
 
+        /// <summary>
+        ///  Creates default queryable options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_queryable_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern QueryableOptions zenoh_queryable_options_default();
+
+        /// <summary>
+        ///  Declares a queryable with options (complete flag and allowed origin).
+        ///  Pass NULL options for defaults.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_queryable_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_queryable_with_options(void* session, byte* key_expr, QueryableOptions* options, zenoh_declare_queryable_with_options_callback_delegate callback, void* context);
+
         /// <summary>
         ///  Replies to a query with data.
-        ///  The query handle is consumed by this operation.
+        ///  The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
         ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_query_reply", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ZenohError zenoh_query_reply(void* query, byte* key_expr, byte* payload, nuint payload_len);
 
+        /// <summary>
+        ///  Replies to a query with data and a full encoding string
+        ///  (e.g. "image/png"). Pass NULL for the default encoding.
+        ///  The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_reply_with_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_query_reply_with_encoding(void* query, byte* key_expr, byte* payload, nuint payload_len, byte* encoding);
+
+        /// <summary>
+        ///  Creates default reply options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_reply_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ReplyOptions zenoh_reply_options_default();
+
+        /// <summary>
+        ///  Replies to a query with data, encoding, attachment, timestamp and QoS options.
+        ///  Pass NULL options for defaults.
+        ///  The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_reply_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_query_reply_with_options(void* query, byte* key_expr, byte* payload, nuint payload_len, ReplyOptions* options);
+
+        /// <summary>
+        ///  Replies to a query with an error, telling the requester the query was rejected.
+        ///  Pass NULL encoding for the default encoding.
+        ///  The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_reply_err", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_query_reply_err(void* query, byte* payload, nuint payload_len, byte* encoding);
+
+        /// <summary>
+        ///  Replies to a query with a delete sample for the given key expression
+        ///  (e.g. to report a tombstoned key).
+        ///  The query handle is borrowed: multiple replies may be sent before calling zenoh_query_finalize.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_reply_del", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_query_reply_del(void* query, byte* key_expr);
+
+        /// <summary>
+        ///  Finalizes a query and frees its handle.
+        ///  Signals the requester that no more replies will be sent. Must be called exactly
+        ///  once per query received by a queryable callback, whether or not it was replied to.
+        ///  The handle must not be used after this call.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_finalize", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_query_finalize(void* query);
+
+        /// <summary>
+        ///  Drops (frees) a query without replying.
+        ///  Equivalent to zenoh_query_finalize.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_drop", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_query_drop(void* query);
+
         /// <summary>
         ///  Gets the selector (key expression) of a query.
         ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  The selector stays readable if the queryable is undeclared first.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_query_selector", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern byte* zenoh_query_selector(void* query);
 
+        /// <summary>
+        ///  Gets the raw parameters of the query selector (the part after '?', e.g. "a=1;b=2").
+        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  Returns an empty string if the selector has no parameters.
+        ///  The parameters stay readable if the queryable is undeclared first.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_parameters", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_query_parameters(void* query);
+
+        /// <summary>
+        ///  Looks up a selector parameter of the query by name, decoding percent escapes as
+        ///  zenoh_selector_parameters does.
+        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  Returns NULL without setting an error if the parameter is not present.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_parameter", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_query_parameter(void* query, byte* name);
+
+        /// <summary>
+        ///  Gets the payload sent with the query.
+        ///  Writes the payload length to out_len and returns a pointer to the payload bytes,
+        ///  valid until the handle is freed with zenoh_query_finalize. The payload stays
+        ///  readable if the queryable is undeclared first.
+        ///  Returns NULL (and writes 0) without setting an error if the query has no payload.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_payload", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_query_payload(void* query, nuint* out_len);
+
+        /// <summary>
+        ///  Gets the full encoding string of the query payload (e.g. "application/json").
+        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  Returns NULL without setting an error if the query has no payload.
+        ///  The encoding stays readable if the queryable is undeclared first.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_query_encoding(void* query);
+
+        /// <summary>
+        ///  Gets the raw attachment bytes sent with the query.
+        ///  Writes the length to out_len and returns a pointer valid until the handle is freed
+        ///  with zenoh_query_finalize. The attachment stays readable if the queryable is undeclared first.
+        ///  Use zenoh_attachment_parse to decode the items.
+        ///  Returns NULL (and writes 0) without setting an error if the query has no attachment.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_attachment", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_query_attachment(void* query, nuint* out_len);
+
         /// <summary>
         ///  Undeclares and frees a queryable.
         /// </summary>
@@ -149,6 +424,7 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Declares a publisher with options.
         ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_declare_publisher_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void* zenoh_declare_publisher_with_options(void* session, byte* key_expr, PublisherOptions* options);
@@ -156,6 +432,7 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Deletes data for a key expression.
         ///  Returns ZenohError code.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_delete", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ZenohError zenoh_delete(void* session, byte* key_expr);
@@ -163,6 +440,7 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Deletes data using a publisher.
         ///  Returns ZenohError code.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_publisher_delete", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ZenohError zenoh_publisher_delete(void* publisher);
@@ -170,13 +448,59 @@ namespace ZenohDotNet.Native.FFI
         /// <summary>
         ///  Put data directly on a session (without declaring a publisher).
         ///  Returns ZenohError code.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_put", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern ZenohError zenoh_put(void* session, byte* key_expr, byte* payload, nuint payload_len);
 
+        /// <summary>
+        ///  Checks whether the publisher currently has matching subscribers.
+        ///  Writes the result to out_matching.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_has_matching_subscribers", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_publisher_has_matching_subscribers(void* publisher, bool* out_matching);
+
+        /// <summary>
+        ///  Declares a matching listener on a publisher.
+        ///  The callback receives (matching, context) each time the publisher gains its first
+        ///  matching subscriber or loses its last one.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Free it with zenoh_undeclare_matching_listener.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_declare_matching_listener", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_publisher_declare_matching_listener(void* publisher, zenoh_publisher_declare_matching_listener_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Checks whether the querier currently has matching queryables.
+        ///  Writes the result to out_matching.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_has_matching_queryables", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_querier_has_matching_queryables(void* querier, bool* out_matching);
+
+        /// <summary>
+        ///  Declares a matching listener on a querier.
+        ///  The callback receives (matching, context) each time the querier gains its first
+        ///  matching queryable or loses its last one.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Free it with zenoh_undeclare_matching_listener.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_declare_matching_listener", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_querier_declare_matching_listener(void* querier, zenoh_querier_declare_matching_listener_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Undeclares and frees a matching listener.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_matching_listener", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_undeclare_matching_listener(void* listener);
+
         /// <summary>
         ///  Declares a liveliness token for the given key expression.
         ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_liveliness_declare_token", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void* zenoh_liveliness_declare_token(void* session, byte* key_expr);
@@ -191,128 +515,1199 @@ namespace ZenohDotNet.Native.FFI
         ///  Declares a liveliness subscriber.
         ///  The callback receives (key_expr, is_alive, context).
         ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
         [DllImport(__DllName, EntryPoint = "zenoh_liveliness_declare_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
         internal static extern void* zenoh_liveliness_declare_subscriber(void* session, byte* key_expr, zenoh_liveliness_declare_subscriber_callback_delegate callback, void* context);
 
         /// <summary>
-        ///  Gets the Zenoh ID of the session as a hex string.
-        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  Creates default liveliness subscriber options
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_session_zid", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern byte* zenoh_session_zid(void* session);
+        [DllImport(__DllName, EntryPoint = "zenoh_liveliness_subscriber_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern LivelinessSubscriberOptions zenoh_liveliness_subscriber_options_default();
 
         /// <summary>
-        ///  Publishes data with encoding on the given publisher.
+        ///  Declares a liveliness subscriber with options.
+        ///  With `history` enabled, the callback first receives (key_expr, true, context) for
+        ///  every token already alive, then live changes.
+        ///  Pass NULL options for defaults.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_publisher_put_with_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern ZenohError zenoh_publisher_put_with_encoding(void* publisher, byte* payload, nuint payload_len, ZenohEncodingId encoding_id);
+        [DllImport(__DllName, EntryPoint = "zenoh_liveliness_declare_subscriber_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_liveliness_declare_subscriber_with_options(void* session, byte* key_expr, LivelinessSubscriberOptions* options, zenoh_liveliness_declare_subscriber_with_options_callback_delegate callback, void* context);
 
         /// <summary>
-        ///  Puts data directly on a key expression with encoding.
+        ///  Queries the liveliness tokens currently alive on the given key expression.
+        ///  The callback receives (key_expr, true, context) once per alive token.
+        ///  Pass 0 as timeout_ms to use the session default.
+        ///  Blocks until all replies have been received or the query times out.
+        ///  The key_expr pointer is valid only during the callback invocation.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_put_with_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern ZenohError zenoh_put_with_encoding(void* session, byte* key_expr, byte* payload, nuint payload_len, ZenohEncodingId encoding_id);
+        [DllImport(__DllName, EntryPoint = "zenoh_liveliness_get", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_liveliness_get(void* session, byte* key_expr, zenoh_liveliness_get_callback_delegate callback, void* context, ulong timeout_ms);
 
         /// <summary>
-        ///  Puts data with attachment on a key expression.
+        ///  Queries the liveliness tokens currently alive without blocking the calling thread.
+        ///  The callback receives (key_expr, true, context) once per alive token on a runtime
+        ///  thread, then `done_callback` is invoked exactly once when the query completes.
+        ///  Pass 0 as timeout_ms to use the session default.
+        ///  `context` must remain valid until `done_callback` has been invoked, or until the
+        ///  session is closed, which discards callbacks still queued in the dispatch queue.
+        ///  Returns a non-Ok code (and never invokes the callbacks) if the arguments are invalid.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_put_with_attachment", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern ZenohError zenoh_put_with_attachment(void* session, byte* key_expr, byte* payload, nuint payload_len, ZenohAttachmentItem* attachment_items, nuint attachment_count);
+        [DllImport(__DllName, EntryPoint = "zenoh_liveliness_get_async", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_liveliness_get_async(void* session, byte* key_expr, zenoh_liveliness_get_async_callback_delegate callback, zenoh_liveliness_get_async_done_callback_delegate done_callback, void* context, ulong timeout_ms);
 
         /// <summary>
-        ///  Declares a querier for repeated queries on the same key expression.
+        ///  Declares a presence tracker that maintains the set of alive liveliness tokens
+        ///  matching the given key expression.
+        ///  The callback receives (key_expr, true, context) when a token joins and
+        ///  (key_expr, false, context) when it leaves. Tokens alive at declaration time are
+        ///  reported as joins before this function returns, unless the session's dispatch
+        ///  queue is enabled, in which case they are queued and reported by zenoh_session_pump.
+        ///  Blocks until the initial liveliness query completes; pass 0 as timeout_ms to use
+        ///  the session default.
+        ///  Events are delivered one at a time in the order the tracker state changed. The
+        ///  callback is invoked after the state lock has been released, so it may query the
+        ///  same tracker with zenoh_presence_snapshot, _count or _contains.
+        ///
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_declare_querier", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern void* zenoh_declare_querier(void* session, byte* key_expr);
+        [DllImport(__DllName, EntryPoint = "zenoh_presence_declare", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_presence_declare(void* session, byte* key_expr, zenoh_presence_declare_callback_delegate callback, void* context, ulong timeout_ms);
 
         /// <summary>
-        ///  Performs a get query using the querier.
+        ///  Enumerates the currently alive keys of a presence tracker in sorted order.
+        ///  The callback receives (key_expr, context) once per key alive when the call was made.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_querier_get", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern ZenohError zenoh_querier_get(void* querier, zenoh_querier_get_callback_delegate callback, void* context);
+        [DllImport(__DllName, EntryPoint = "zenoh_presence_snapshot", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_presence_snapshot(void* tracker, zenoh_presence_snapshot_callback_delegate callback, void* context);
 
         /// <summary>
-        ///  Undeclares and frees a querier.
+        ///  Returns the number of currently alive keys of a presence tracker (0 on error).
         /// </summary>
-        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_querier", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
-        internal static extern void zenoh_undeclare_querier(void* querier);
+        [DllImport(__DllName, EntryPoint = "zenoh_presence_count", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern nuint zenoh_presence_count(void* tracker);
 
+        /// <summary>
+        ///  Returns true if the given key is currently alive in a presence tracker.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_presence_contains", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool zenoh_presence_contains(void* tracker, byte* key_expr);
 
-    }
+        /// <summary>
+        ///  Undeclares and frees a presence tracker.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_presence_undeclare", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_presence_undeclare(void* tracker);
 
-    /// <summary>
-    ///  Publisher options
-    /// </summary>
-    [StructLayout(LayoutKind.Sequential)]
-    internal unsafe partial struct PublisherOptions
-    {
-        public ZenohCongestionControl congestion_control;
-        public ZenohPriority priority;
-        [MarshalAs(UnmanagedType.U1)] public bool is_express;
-    }
+        /// <summary>
+        ///  Validates a key expression.
+        ///  Returns Ok if it is a valid canonical key expression, InvalidKeyExpr otherwise;
+        ///  zenoh_last_error() then describes precisely what is wrong.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_validate", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_keyexpr_validate(byte* key_expr);
 
-    /// <summary>
-    ///  Timestamp structure
-    /// </summary>
-    [StructLayout(LayoutKind.Sequential)]
-    internal unsafe partial struct ZenohTimestamp
-    {
         /// <summary>
-        ///  NTP64 timestamp (seconds since epoch in upper 32 bits, fraction in lower 32 bits)
+        ///  Canonizes a key expression (e.g. "a/**/**/b" becomes "a/**/b").
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        public ulong time_ntp64;
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_canonize", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_keyexpr_canonize(byte* key_expr);
+
         /// <summary>
-        ///  Unique ID of the timestamp source (first 16 bytes of ZenohId)
+        ///  Checks whether two key expressions intersect (at least one key matches both).
+        ///  Writes the result to out_result.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        public fixed byte id[16];
-    }
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_intersects", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_keyexpr_intersects(byte* left, byte* right, bool* out_result);
 
-    /// <summary>
-    ///  Attachment key-value pair
-    /// </summary>
-    [StructLayout(LayoutKind.Sequential)]
-    internal unsafe partial struct ZenohAttachmentItem
-    {
-        public byte* key;
-        public byte* value;
-        public nuint value_len;
-    }
+        /// <summary>
+        ///  Checks whether `left` includes `right` (every key matching right also matches left).
+        ///  Writes the result to out_result.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_includes", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_keyexpr_includes(byte* left, byte* right, bool* out_result);
 
-    /// <summary>
-    ///  Sample data structure passed to subscriber callbacks
-    /// </summary>
-    [StructLayout(LayoutKind.Sequential)]
-    internal unsafe partial struct SampleData
-    {
-        public byte* key_expr;
-        public byte* payload_data;
-        public nuint payload_len;
-        public ZenohSampleKind kind;
-        public ZenohEncodingId encoding_id;
-        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
-        public ZenohTimestamp timestamp;
-    }
+        /// <summary>
+        ///  Joins two key expressions with a '/' separator and canonizes the result.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_join", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_keyexpr_join(byte* left, byte* right);
 
+        /// <summary>
+        ///  Concatenates a suffix to a key expression without a separator
+        ///  (e.g. "a/b" + "c" gives "a/bc"). Fails if the result would be invalid,
+        ///  for instance when `left` ends with a wildcard.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keyexpr_concat", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_keyexpr_concat(byte* left, byte* right);
 
-    /// <summary>
-    ///  Congestion control strategy
-    /// </summary>
-    internal enum ZenohCongestionControl : uint
-    {
         /// <summary>
-        ///  Block if the buffer is full
+        ///  Compiles a key expression format such as "game/player/${id:*}/position".
+        ///  Each "${name:pattern}" placeholder must be a whole chunk and is filled or
+        ///  extracted with zenoh_keformat_format and zenoh_keformat_parse.
+        ///  Returns a pointer that must be freed with zenoh_keformat_free, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
         /// </summary>
-        Block = 0,
+        [DllImport(__DllName, EntryPoint = "zenoh_keformat_new", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_keformat_new(byte* spec);
+
         /// <summary>
-        ///  Drop the message if the buffer is full
+        ///  Frees a key expression format.
         /// </summary>
-        Drop = 1,
-    }
+        [DllImport(__DllName, EntryPoint = "zenoh_keformat_free", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_keformat_free(void* format);
 
-    /// <summary>
-    ///  Priority of messages
-    /// </summary>
+        /// <summary>
+        ///  Builds a key expression from a format and named values.
+        ///  Every value is validated against its placeholder pattern, so a value such as
+        ///  "a/b" is rejected for a "${id:*}" placeholder instead of producing a wrong key.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keformat_format", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_keformat_format(void* format, ZenohKeFormatValue* values, nuint value_count);
+
+        /// <summary>
+        ///  Parses a key expression against a format and reports every placeholder.
+        ///  The callback receives (id, value, context) once per placeholder, in format order;
+        ///  value is an empty string when a "**" placeholder matched nothing.
+        ///  Returns InvalidKeyExpr if the key does not match the format.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keformat_parse", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_keformat_parse(void* format, byte* key_expr, zenoh_keformat_parse_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Extracts a single named value from a key expression parsed against a format.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure
+        ///  (including when the key does not match the format or the id is unknown).
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_keformat_parse_value", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_keformat_parse_value(void* format, byte* key_expr, byte* id);
+
+        /// <summary>
+        ///  Builds a selector string from a key expression and parameter pairs
+        ///  (e.g. "sensors/**" with [("_time", "[now(-1h)..]"), ("unit", "C")] gives
+        ///  "sensors/**?_time=[now(-1h)..];unit=C").
+        ///  Names must be non-empty. The characters ';', '=', '&amp;' and '%' in names and values
+        ///  are percent-escaped (e.g. "a;b" becomes "a%3Bb"); zenoh_selector_parameters,
+        ///  zenoh_selector_parameter and zenoh_query_parameter decode them again.
+        ///  A "_time" value is validated as a time range.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_selector_build", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_selector_build(byte* key_expr, ZenohSelectorParameter* parameters, nuint parameter_count);
+
+        /// <summary>
+        ///  Returns the key expression part of a selector.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_selector_key_expr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_selector_key_expr(byte* selector);
+
+        /// <summary>
+        ///  Enumerates the parameters of a selector, decoding percent escapes.
+        ///  The callback receives (name, value, context) once per parameter, in selector order.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_selector_parameters", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_selector_parameters(byte* selector, zenoh_selector_parameters_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Looks up a selector parameter by name, decoding percent escapes as
+        ///  zenoh_selector_parameters does.
+        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  Returns NULL without setting an error if the parameter is not present.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_selector_parameter", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_selector_parameter(byte* selector, byte* name);
+
+        /// <summary>
+        ///  Reads the reserved "_time" parameter of a selector, resolving relative times
+        ///  such as "now(-1h)" against the current time.
+        ///  Writes false to out_present if the selector has no "_time" parameter.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_selector_time_range", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_selector_time_range(byte* selector, bool* out_present, ZenohTimeBound* out_start, ZenohTimeBound* out_end);
+
+        /// <summary>
+        ///  Reads the reserved "_time" parameter of a query's selector.
+        ///  Behaves like zenoh_selector_time_range otherwise.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_query_time_range", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_query_time_range(void* query, bool* out_present, ZenohTimeBound* out_start, ZenohTimeBound* out_end);
+
+        /// <summary>
+        ///  Declares a key expression on the session so that it is sent on the wire as a
+        ///  numeric id instead of the full string.
+        ///  The handle can be passed to the *_keyexpr variants of put, delete, get,
+        ///  subscriber and publisher declaration on the same session; those reject a handle
+        ///  declared on another session with InvalidKeyExpr (or NULL).
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_keyexpr(void* session, byte* key_expr);
+
+        /// <summary>
+        ///  Undeclares and frees a declared key expression.
+        ///  Entities declared with it keep working; only the wire mapping is released.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_undeclare_keyexpr(void* keyexpr);
+
+        /// <summary>
+        ///  Puts data on a declared key expression with options.
+        ///  Pass NULL options for defaults.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_put_keyexpr(void* session, void* keyexpr, byte* payload, nuint payload_len, PutOptions* options);
+
+        /// <summary>
+        ///  Deletes data for a declared key expression.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_delete_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_delete_keyexpr(void* session, void* keyexpr);
+
+        /// <summary>
+        ///  Performs a get query on a declared key expression with optional selector
+        ///  parameters (e.g. "a=1;b=2", may be NULL) and options.
+        ///  Behaves like zenoh_get_with_options otherwise.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_get_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_get_keyexpr(void* session, void* keyexpr, byte* parameters, GetOptions* options, zenoh_get_keyexpr_callback_delegate callback, zenoh_get_keyexpr_error_callback_delegate error_callback, void* context);
+
+        /// <summary>
+        ///  Declares a subscriber on a declared key expression with a callback.
+        ///  Behaves like zenoh_declare_subscriber otherwise.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_subscriber_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_subscriber_keyexpr(void* session, void* keyexpr, zenoh_declare_subscriber_keyexpr_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Declares a publisher on a declared key expression with options.
+        ///  Pass NULL options for defaults.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_publisher_keyexpr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_publisher_keyexpr(void* session, void* keyexpr, PublisherOptions* options);
+
+        /// <summary>
+        ///  Declares a pull subscriber whose samples are buffered natively until the
+        ///  application receives them with zenoh_pull_subscriber_try_recv,
+        ///  zenoh_pull_subscriber_recv_timeout or zenoh_pull_subscriber_recv_batch.
+        ///  Pass 0 as capacity for the default (256 samples).
+        ///  Ring (the default) never blocks the network side. Fifo loses no samples but applies
+        ///  backpressure to the whole session: if the application stops receiving, all
+        ///  traffic of the session stalls until it drains the buffer.
+        ///  Receive calls may be made from several threads; they are served one at a time and
+        ///  samples are handed out in arrival order.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_pull_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_pull_subscriber(void* session, byte* key_expr, ZenohChannelKind kind, nuint capacity);
+
+        /// <summary>
+        ///  Receives one sample without blocking.
+        ///  Writes false to out_received if no sample is available.
+        ///  Returns BufferTooSmall with the required sizes in the buffer if the sample does
+        ///  not fit; the sample is kept and returned by the next receive call.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_pull_subscriber_try_recv", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_pull_subscriber_try_recv(void* subscriber, ZenohSampleBuffer* buffer, bool* out_received);
+
+        /// <summary>
+        ///  Receives one sample, waiting up to timeout_ms milliseconds for one to arrive
+        ///  (0 does not wait).
+        ///  Behaves like zenoh_pull_subscriber_try_recv otherwise.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_pull_subscriber_recv_timeout", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_pull_subscriber_recv_timeout(void* subscriber, ZenohSampleBuffer* buffer, ulong timeout_ms, bool* out_received);
+
+        /// <summary>
+        ///  Receives up to buffer_count samples into consecutive buffers.
+        ///  Waits up to timeout_ms milliseconds for the first sample (0 does not wait), then
+        ///  takes whatever else is already buffered without waiting.
+        ///  Writes the number of filled buffers to out_received. If a sample does not fit in
+        ///  its buffer, the samples received so far are reported, BufferTooSmall is returned
+        ///  and that buffer holds the required sizes.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_pull_subscriber_recv_batch", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_pull_subscriber_recv_batch(void* subscriber, ZenohSampleBuffer* buffers, nuint buffer_count, ulong timeout_ms, nuint* out_received);
+
+        /// <summary>
+        ///  Undeclares and frees a pull subscriber. Samples not yet received are discarded.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_pull_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_undeclare_pull_subscriber(void* subscriber);
+
+        /// <summary>
+        ///  Declares a subscriber with an allocation-free delivery path.
+        ///  Instead of SampleData, the callback receives a SampleView: the key expression is
+        ///  passed as pointer plus length, and the payload is exposed without copying, as a
+        ///  single pointer when contiguous and as a slice list otherwise. The encoding string
+        ///  and attachment are exposed the same way. Buffers are reused per thread, so
+        ///  steady-state delivery performs no heap allocation.
+        ///  When the session's dispatch queue is enabled, each queued sample still costs one
+        ///  allocation for the queue entry.
+        ///  Returns a pointer on success, NULL on failure. Free it with zenoh_undeclare_subscriber.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_subscriber_view", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_subscriber_view(void* session, byte* key_expr, zenoh_declare_subscriber_view_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Creates an owned, reference-counted handle to a sample delivered to a callback.
+        ///  `sample` is the `sample` field of a SampleData or SampleView and must be passed
+        ///  during the callback. The payload is shared with the original sample, not copied.
+        ///  Returns a handle that must be released with zenoh_sample_drop, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_clone", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohOwnedSample* zenoh_sample_clone(ZenohSampleRef* sample);
+
+        /// <summary>
+        ///  Adds a reference to a sample handle, e.g. before handing it to another thread.
+        ///  Each reference must be released with zenoh_sample_drop.
+        ///  Returns the same handle, or NULL if it is NULL.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_retain", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohOwnedSample* zenoh_sample_retain(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Releases a reference to a sample handle; the sample is freed with the last one.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_drop", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_sample_drop(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the key expression of a sample handle.
+        ///  Writes the length to out_len and returns a pointer to the UTF-8 bytes
+        ///  (not NUL-terminated), valid until the handle is released.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_key_expr", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_sample_key_expr(ZenohOwnedSample* sample, nuint* out_len);
+
+        /// <summary>
+        ///  Gets the total payload length of a sample handle, or 0 on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_payload_len", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern nuint zenoh_sample_payload_len(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the number of contiguous slices the payload of a sample handle consists of,
+        ///  or 0 on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_payload_slice_count", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern nuint zenoh_sample_payload_slice_count(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the payload slices of a sample handle without copying, in a single pass.
+        ///  Writes up to `capacity` slices to out_slices, each valid until the handle is
+        ///  released, and returns the total number of slices; a result larger than capacity
+        ///  means out_slices was too small. out_slices may be NULL when capacity is 0.
+        ///  Returns 0 on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_payload_slices", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern nuint zenoh_sample_payload_slices(ZenohOwnedSample* sample, ZenohPayloadSlice* out_slices, nuint capacity);
+
+        /// <summary>
+        ///  Gets the kind of a sample handle, or Put on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_kind", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohSampleKind zenoh_sample_kind(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the fast-path encoding id of a sample handle, or Empty on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_encoding_id", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohEncodingId zenoh_sample_encoding_id(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the full encoding string of a sample handle, including any schema suffix.
+        ///  Returns a C string that must be freed with zenoh_free_string, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_sample_encoding(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the timestamp of a sample handle.
+        ///  Returns true and writes it to out_timestamp if the sample has one; returns false
+        ///  without setting an error if it has none.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_timestamp", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool zenoh_sample_timestamp(ZenohOwnedSample* sample, ZenohTimestamp* out_timestamp);
+
+        /// <summary>
+        ///  Gets the attachment of a sample handle.
+        ///  Writes the length to out_len and returns a pointer to the attachment bytes,
+        ///  valid until the handle is released. Use zenoh_attachment_parse to decode items.
+        ///  Returns NULL (and writes 0) without setting an error if the sample has no attachment.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_attachment", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_sample_attachment(ZenohOwnedSample* sample, nuint* out_len);
+
+        /// <summary>
+        ///  Gets the priority of a sample handle, or Data on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_priority", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohPriority zenoh_sample_priority(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets the congestion control of a sample handle, or Drop on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_congestion_control", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohCongestionControl zenoh_sample_congestion_control(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Gets whether a sample handle was sent with express (unbatched) delivery,
+        ///  or false on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_sample_express", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        [return: MarshalAs(UnmanagedType.U1)]
+        internal static extern bool zenoh_sample_express(ZenohOwnedSample* sample);
+
+        /// <summary>
+        ///  Declares a subscriber that delivers samples in batches to cut the number of
+        ///  callback invocations on high-rate topics.
+        ///  A batch is delivered as soon as it holds max_samples samples, or max_delay_us
+        ///  microseconds after its first sample arrived, whichever comes first. Pass 0 to
+        ///  disable either bound; at least one must be set. The delay is measured by the
+        ///  runtime timer, whose resolution is one millisecond, so a max_delay_us below 1000
+        ///  behaves as roughly 1 ms.
+        ///  The callback receives (samples, count, context) with samples in arrival order.
+        ///  Returns a pointer on success, NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_batch_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_batch_subscriber(void* session, byte* key_expr, nuint max_samples, ulong max_delay_us, zenoh_declare_batch_subscriber_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Delivers the samples accumulated by a batched subscriber immediately,
+        ///  without waiting for either batch bound.
+        ///  May be called from the batch callback: the batch is then delivered right after
+        ///  the callback returns, still in arrival order.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_batch_subscriber_flush", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_batch_subscriber_flush(void* subscriber);
+
+        /// <summary>
+        ///  Undeclares and frees a batched subscriber.
+        ///  Samples not yet delivered are discarded; call zenoh_batch_subscriber_flush first
+        ///  to receive them.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_batch_subscriber", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_undeclare_batch_subscriber(void* subscriber);
+
+        /// <summary>
+        ///  Enables the dispatch queue of a session.
+        ///  While enabled, subscriber, queryable, liveliness, presence, matching listener,
+        ///  asynchronous get and querier callbacks are not invoked on Zenoh runtime threads but
+        ///  queued, and run on the thread that calls zenoh_session_pump. Callbacks of calls that block until
+        ///  completion (zenoh_get, zenoh_liveliness_get) already run on the calling thread.
+        ///  At most `capacity` events are held (0 for the default of 4096); further events are
+        ///  dropped and counted. Queued events of an undeclared entity are discarded, as are
+        ///  queued asynchronous get callbacks once the session is closed.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_session_enable_dispatch_queue", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_session_enable_dispatch_queue(void* session, nuint capacity);
+
+        /// <summary>
+        ///  Disables the dispatch queue of a session; callbacks run inline again.
+        ///  Events already queued stay queued until pumped.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_session_disable_dispatch_queue", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_session_disable_dispatch_queue(void* session);
+
+        /// <summary>
+        ///  Invokes queued callbacks of a session on the calling thread, in arrival order.
+        ///  Stops after max_events callbacks (0 for no limit) or once budget_us microseconds
+        ///  have elapsed (0 for no limit), whichever comes first; a callback that is already
+        ///  running is never interrupted.
+        ///  Returns the number of callbacks invoked, or -1 on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_session_pump", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern long zenoh_session_pump(void* session, nuint max_events, ulong budget_us);
+
+        /// <summary>
+        ///  Reads the dispatch queue statistics of a session.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_session_dispatch_stats", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_session_dispatch_stats(void* session, ZenohDispatchStats* out_stats);
+
+        /// <summary>
+        ///  Gets the Zenoh ID of the session as a hex string.
+        ///  Returns a C string that must be freed with zenoh_free_string.
+        ///  The format is a stable hex representation of the ZenohId bytes.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_session_zid", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern byte* zenoh_session_zid(void* session);
+
+        /// <summary>
+        ///  Parses attachment bytes in the key_len|key|value_len|value format
+        ///  (as produced by zenoh_put_with_attachment and delivered in SampleData).
+        ///  On success, writes the items pointer and count to out_items/out_count and
+        ///  returns a handle that owns them; free it with zenoh_attachment_free.
+        ///  Returns NULL on failure.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_attachment_parse", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_attachment_parse(byte* data, nuint data_len, ZenohAttachmentItem** out_items, nuint* out_count);
+
+        /// <summary>
+        ///  Frees a parsed attachment returned by zenoh_attachment_parse.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_attachment_free", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_attachment_free(void* attachment);
+
+        /// <summary>
+        ///  Publishes data with encoding on the given publisher.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_put_with_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_publisher_put_with_encoding(void* publisher, byte* payload, nuint payload_len, ZenohEncodingId encoding_id);
+
+        /// <summary>
+        ///  Puts data directly on a key expression with encoding.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_with_encoding", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_put_with_encoding(void* session, byte* key_expr, byte* payload, nuint payload_len, ZenohEncodingId encoding_id);
+
+        /// <summary>
+        ///  Publishes data on the given publisher with a full encoding string
+        ///  (e.g. "application/protobuf;my.Message"). Pass NULL for the default encoding.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_put_with_encoding_str", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_publisher_put_with_encoding_str(void* publisher, byte* payload, nuint payload_len, byte* encoding);
+
+        /// <summary>
+        ///  Puts data directly on a key expression with a full encoding string
+        ///  (e.g. "application/x-ros2-cdr"). Pass NULL for the default encoding.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_with_encoding_str", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_put_with_encoding_str(void* session, byte* key_expr, byte* payload, nuint payload_len, byte* encoding);
+
+        /// <summary>
+        ///  Puts data with attachment on a key expression.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_with_attachment", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_put_with_attachment(void* session, byte* key_expr, byte* payload, nuint payload_len, ZenohAttachmentItem* attachment_items, nuint attachment_count);
+
+        /// <summary>
+        ///  Creates default put options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern PutOptions zenoh_put_options_default();
+
+        /// <summary>
+        ///  Puts data on a key expression with encoding, attachment, QoS and timestamp options.
+        ///  Pass NULL options for defaults.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_put_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_put_with_options(void* session, byte* key_expr, byte* payload, nuint payload_len, PutOptions* options);
+
+        /// <summary>
+        ///  Creates default publisher put options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_put_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern PublisherPutOptions zenoh_publisher_put_options_default();
+
+        /// <summary>
+        ///  Publishes data on the given publisher with encoding, attachment and timestamp options.
+        ///  Pass NULL options for defaults.
+        ///  QoS is configured when the publisher is declared (see zenoh_declare_publisher_with_options).
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_publisher_put_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_publisher_put_with_options(void* publisher, byte* payload, nuint payload_len, PublisherPutOptions* options);
+
+        /// <summary>
+        ///  Declares a querier for repeated queries on the same key expression.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_querier", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_querier(void* session, byte* key_expr);
+
+        /// <summary>
+        ///  Creates default querier options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern QuerierOptions zenoh_querier_options_default();
+
+        /// <summary>
+        ///  Declares a querier with target, consolidation, timeout, QoS and destination options.
+        ///  Pass NULL options for defaults.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_declare_querier_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void* zenoh_declare_querier_with_options(void* session, byte* key_expr, QuerierOptions* options);
+
+        /// <summary>
+        ///  Performs a get query using the querier.
+        ///  The callback receives SampleData pointers that are valid only during the callback invocation.
+        ///  Do not store these pointers for later use.
+        ///  Error replies are discarded; use zenoh_querier_get_with_error_callback to receive them.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_get", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_querier_get(void* querier, zenoh_querier_get_callback_delegate callback, void* context);
+
+        /// <summary>
+        ///  Performs a get query using the querier, delivering error replies to `error_callback`.
+        ///  The callbacks receive pointers that are valid only during the callback invocation.
+        ///  Do not store these pointers for later use.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_get_with_error_callback", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_querier_get_with_error_callback(void* querier, zenoh_querier_get_with_error_callback_callback_delegate callback, zenoh_querier_get_with_error_callback_error_callback_delegate error_callback, void* context);
+
+        /// <summary>
+        ///  Creates default querier get options
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_get_options_default", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern QuerierGetOptions zenoh_querier_get_options_default();
+
+        /// <summary>
+        ///  Performs a get query using the querier with selector parameters, payload,
+        ///  encoding and attachment. Pass NULL options for a bare get.
+        ///  Error replies are delivered to `error_callback`.
+        ///  The callbacks receive pointers that are valid only during the callback invocation.
+        ///  Do not store these pointers for later use.
+        ///  Call zenoh_last_error() for error details.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_querier_get_with_options", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern ZenohError zenoh_querier_get_with_options(void* querier, QuerierGetOptions* options, zenoh_querier_get_with_options_callback_delegate callback, zenoh_querier_get_with_options_error_callback_delegate error_callback, void* context);
+
+        /// <summary>
+        ///  Undeclares and frees a querier.
+        /// </summary>
+        [DllImport(__DllName, EntryPoint = "zenoh_undeclare_querier", CallingConvention = CallingConvention.Cdecl, ExactSpelling = true)]
+        internal static extern void zenoh_undeclare_querier(void* querier);
+
+
+    }
+
+    /// <summary>
+    ///  Publisher options
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct PublisherOptions
+    {
+        public ZenohCongestionControl congestion_control;
+        public ZenohPriority priority;
+        [MarshalAs(UnmanagedType.U1)] public bool is_express;
+    }
+
+    /// <summary>
+    ///  Timestamp structure
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohTimestamp
+    {
+        /// <summary>
+        ///  NTP64 timestamp (seconds since epoch in upper 32 bits, fraction in lower 32 bits)
+        /// </summary>
+        public ulong time_ntp64;
+        /// <summary>
+        ///  Unique ID of the timestamp source (first 16 bytes of ZenohId)
+        /// </summary>
+        public fixed byte id[16];
+    }
+
+    /// <summary>
+    ///  Attachment key-value pair
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohAttachmentItem
+    {
+        public byte* key;
+        public byte* value;
+        public nuint value_len;
+    }
+
+    /// <summary>
+    ///  Named value for key expression formats
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohKeFormatValue
+    {
+        /// <summary>
+        ///  Placeholder id, e.g. "id" for "${id:*}"
+        /// </summary>
+        public byte* id;
+        public byte* value;
+    }
+
+    /// <summary>
+    ///  Selector parameter name-value pair
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohSelectorParameter
+    {
+        public byte* name;
+        public byte* value;
+    }
+
+    /// <summary>
+    ///  Resolved bound of a "_time" selector parameter
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohTimeBound
+    {
+        public ZenohTimeBoundKind kind;
+        /// <summary>
+        ///  NTP64 time of the bound; 0 when unbounded
+        /// </summary>
+        public ulong time_ntp64;
+    }
+
+    /// <summary>
+    ///  Options for put operations.
+    ///  Use zenoh_put_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct PutOptions
+    {
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string (e.g. "application/x-ros2-cdr"); overrides encoding_id when not NULL
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Attachment items (may be NULL if attachment_count is 0)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+        public ZenohCongestionControl congestion_control;
+        public ZenohPriority priority;
+        [MarshalAs(UnmanagedType.U1)] public bool is_express;
+        /// <summary>
+        ///  When true, `timestamp` is sent with the sample.
+        ///  An all-zero timestamp id is replaced by the session's own id.
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+        public ZenohLocality allowed_destination;
+    }
+
+    /// <summary>
+    ///  Options for publishing on a declared publisher.
+    ///  QoS (congestion control, priority, express, allowed destination) is fixed when
+    ///  the publisher is declared, so it is not part of these options.
+    ///  Use zenoh_publisher_put_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct PublisherPutOptions
+    {
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string; overrides encoding_id when not NULL
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Attachment items (may be NULL if attachment_count is 0)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+        /// <summary>
+        ///  When true, `timestamp` is sent with the sample.
+        ///  An all-zero timestamp id is replaced by the session's own id.
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+    }
+
+    /// <summary>
+    ///  Queryable declaration options
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct QueryableOptions
+    {
+        /// <summary>
+        ///  Advertise the queryable as complete for its key expression,
+        ///  so it is reached by QueryTarget::AllComplete queries
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool complete;
+        /// <summary>
+        ///  Restrict which queries are received (session-local, remote or any)
+        /// </summary>
+        public ZenohLocality allowed_origin;
+    }
+
+    /// <summary>
+    ///  Options for query replies.
+    ///  Use zenoh_reply_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ReplyOptions
+    {
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string; overrides encoding_id when not NULL
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Attachment items (may be NULL if attachment_count is 0)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+        /// <summary>
+        ///  When true, `timestamp` is sent with the reply.
+        ///  An all-zero timestamp id is replaced by the queryable session's id.
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+        public ZenohCongestionControl congestion_control;
+        public ZenohPriority priority;
+        [MarshalAs(UnmanagedType.U1)] public bool is_express;
+    }
+
+    /// <summary>
+    ///  Options for get operations.
+    ///  Use zenoh_get_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct GetOptions
+    {
+        public ZenohQueryTarget target;
+        public ZenohConsolidationMode consolidation;
+        /// <summary>
+        ///  Query timeout in milliseconds (0 uses the session default)
+        /// </summary>
+        public ulong timeout_ms;
+        /// <summary>
+        ///  Query payload (may be NULL if payload_len is 0; NULL sends no payload)
+        /// </summary>
+        public byte* payload;
+        public nuint payload_len;
+        /// <summary>
+        ///  Encoding of the query payload (ignored when no payload is sent)
+        /// </summary>
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string; overrides encoding_id when not NULL
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Attachment items (may be NULL if attachment_count is 0)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+        public ZenohLocality allowed_destination;
+    }
+
+    /// <summary>
+    ///  Querier declaration options.
+    ///  Use zenoh_querier_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct QuerierOptions
+    {
+        public ZenohQueryTarget target;
+        public ZenohConsolidationMode consolidation;
+        /// <summary>
+        ///  Query timeout in milliseconds (0 uses the session default)
+        /// </summary>
+        public ulong timeout_ms;
+        public ZenohCongestionControl congestion_control;
+        public ZenohPriority priority;
+        [MarshalAs(UnmanagedType.U1)] public bool is_express;
+        public ZenohLocality allowed_destination;
+    }
+
+    /// <summary>
+    ///  Options for a single querier get.
+    ///  Use zenoh_querier_get_options_default() to obtain a value with default settings.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct QuerierGetOptions
+    {
+        /// <summary>
+        ///  Selector parameters (e.g. "a=1;b=2"); may be NULL
+        /// </summary>
+        public byte* parameters;
+        /// <summary>
+        ///  Query payload (may be NULL if payload_len is 0; NULL sends no payload)
+        /// </summary>
+        public byte* payload;
+        public nuint payload_len;
+        /// <summary>
+        ///  Encoding of the query payload (ignored when no payload is sent)
+        /// </summary>
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string; overrides encoding_id when not NULL
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Attachment items (may be NULL if attachment_count is 0)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+    }
+
+    /// <summary>
+    ///  Liveliness subscriber options
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct LivelinessSubscriberOptions
+    {
+        /// <summary>
+        ///  Replay the tokens that are already alive when the subscriber is declared
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool history;
+    }
+
+    /// <summary>
+    ///  Opaque reference to a sample, valid only during the callback it was passed to.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohSampleRef
+    {
+    }
+
+    /// <summary>
+    ///  Opaque owned, reference-counted sample created by zenoh_sample_clone.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohOwnedSample
+    {
+    }
+
+    /// <summary>
+    ///  Sample data structure passed to subscriber callbacks
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SampleData
+    {
+        public byte* key_expr;
+        public byte* payload_data;
+        public nuint payload_len;
+        public ZenohSampleKind kind;
+        /// <summary>
+        ///  Fast-path encoding id (AppOctetStream if the encoding is not in the table)
+        /// </summary>
+        public ZenohEncodingId encoding_id;
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+        /// <summary>
+        ///  Full encoding string including the schema suffix (e.g. "application/protobuf;my.Message")
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  Raw attachment bytes (NULL if the sample has no attachment)
+        /// </summary>
+        public byte* attachment_data;
+        public nuint attachment_len;
+        /// <summary>
+        ///  Decoded attachment items (NULL if there is no attachment or it is
+        ///  not in the key_len|key|value_len|value format)
+        /// </summary>
+        public ZenohAttachmentItem* attachment_items;
+        public nuint attachment_count;
+        /// <summary>
+        ///  Reference to the underlying sample, valid only during the callback.
+        ///  Pass it to zenoh_sample_clone to retain the sample beyond the callback.
+        /// </summary>
+        public ZenohSampleRef* sample;
+    }
+
+    /// <summary>
+    ///  Contiguous part of a payload
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohPayloadSlice
+    {
+        public byte* data;
+        public nuint len;
+    }
+
+    /// <summary>
+    ///  Allocation-free view of a sample passed to view subscriber callbacks.
+    ///  All pointers are valid only during the callback invocation.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct SampleView
+    {
+        /// <summary>
+        ///  Key expression bytes (UTF-8, not NUL-terminated)
+        /// </summary>
+        public byte* key_expr;
+        public nuint key_len;
+        /// <summary>
+        ///  Payload when it is contiguous, NULL otherwise; payload_len is always the total length
+        /// </summary>
+        public byte* payload_data;
+        public nuint payload_len;
+        /// <summary>
+        ///  Payload as a list of slices; always set, with a single slice when contiguous
+        /// </summary>
+        public ZenohPayloadSlice* payload_slices;
+        public nuint payload_slice_count;
+        public ZenohSampleKind kind;
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string including the schema suffix (UTF-8, not NUL-terminated)
+        /// </summary>
+        public byte* encoding;
+        public nuint encoding_len;
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+        /// <summary>
+        ///  Raw attachment bytes (NULL if the sample has no attachment)
+        /// </summary>
+        public byte* attachment_data;
+        public nuint attachment_len;
+        /// <summary>
+        ///  Reference to the underlying sample, valid only during the callback.
+        ///  Pass it to zenoh_sample_clone to retain the sample beyond the callback.
+        /// </summary>
+        public ZenohSampleRef* sample;
+    }
+
+    /// <summary>
+    ///  Caller-provided storage for a sample received from a pull subscriber.
+    ///  The key and payload buffers are owned by the caller; the remaining fields are
+    ///  written on receive.
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohSampleBuffer
+    {
+        /// <summary>
+        ///  Buffer for the NUL-terminated key expression
+        /// </summary>
+        public byte* key_buffer;
+        public nuint key_capacity;
+        public byte* payload_buffer;
+        public nuint payload_capacity;
+        /// <summary>
+        ///  Key expression length without the terminating NUL. When BufferTooSmall is
+        ///  returned, key_len and payload_len hold the sizes required.
+        /// </summary>
+        public nuint key_len;
+        public nuint payload_len;
+        public ZenohSampleKind kind;
+        public ZenohEncodingId encoding_id;
+        [MarshalAs(UnmanagedType.U1)] public bool timestamp_valid;
+        public ZenohTimestamp timestamp;
+    }
+
+    /// <summary>
+    ///  Dispatch queue statistics of a session
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ZenohDispatchStats
+    {
+        /// <summary>
+        ///  Events currently waiting to be pumped
+        /// </summary>
+        public nuint queued;
+        /// <summary>
+        ///  Largest queue depth observed since the queue was created
+        /// </summary>
+        public nuint high_water;
+        /// <summary>
+        ///  Events invoked by zenoh_session_pump
+        /// </summary>
+        public ulong dispatched;
+        /// <summary>
+        ///  Events discarded because the queue was full
+        /// </summary>
+        public ulong dropped;
+    }
+
+    /// <summary>
+    ///  Error reply data passed to reply error callbacks
+    /// </summary>
+    [StructLayout(LayoutKind.Sequential)]
+    internal unsafe partial struct ReplyErrorData
+    {
+        public byte* payload_data;
+        public nuint payload_len;
+        public ZenohEncodingId encoding_id;
+        /// <summary>
+        ///  Full encoding string including the schema suffix
+        /// </summary>
+        public byte* encoding;
+        /// <summary>
+        ///  True if replier_id holds the Zenoh ID of the replying session
+        /// </summary>
+        [MarshalAs(UnmanagedType.U1)] public bool replier_id_valid;
+        public fixed byte replier_id[16];
+    }
+
+
+    /// <summary>
+    ///  Congestion control strategy
+    /// </summary>
+    internal enum ZenohCongestionControl : uint
+    {
+        /// <summary>
+        ///  Block if the buffer is full
+        /// </summary>
+        Block = 0,
+        /// <summary>
+        ///  Drop the message if the buffer is full
+        /// </summary>
+        Drop = 1,
+    }
+
+    /// <summary>
+    ///  Priority of messages
+    /// </summary>
     internal enum ZenohPriority : uint
     {
         RealTime = 1,
@@ -324,6 +1719,93 @@ namespace ZenohDotNet.Native.FFI
         Background = 7,
     }
 
+    /// <summary>
+    ///  Locality restriction for the destination of a message
+    /// </summary>
+    internal enum ZenohLocality : uint
+    {
+        /// <summary>
+        ///  Local and remote entities
+        /// </summary>
+        Any = 0,
+        /// <summary>
+        ///  Entities in the same session only
+        /// </summary>
+        SessionLocal = 1,
+        /// <summary>
+        ///  Remote entities only
+        /// </summary>
+        Remote = 2,
+    }
+
+    /// <summary>
+    ///  Kind of a time range bound
+    /// </summary>
+    internal enum ZenohTimeBoundKind : uint
+    {
+        Unbounded = 0,
+        Inclusive = 1,
+        Exclusive = 2,
+    }
+
+    /// <summary>
+    ///  Handler backing a pull subscriber
+    /// </summary>
+    internal enum ZenohChannelKind : uint
+    {
+        /// <summary>
+        ///  Ring buffer; the oldest sample is dropped when the buffer is full
+        /// </summary>
+        Ring = 0,
+        /// <summary>
+        ///  Bounded FIFO; when the buffer is full the session's receive task waits for
+        ///  space, stalling delivery to every subscriber and queryable of the session
+        /// </summary>
+        Fifo = 1,
+    }
+
+    /// <summary>
+    ///  Which queryables a query should target
+    /// </summary>
+    internal enum ZenohQueryTarget : uint
+    {
+        /// <summary>
+        ///  The nearest complete queryable if any, otherwise all matching queryables
+        /// </summary>
+        BestMatching = 0,
+        /// <summary>
+        ///  All matching queryables
+        /// </summary>
+        All = 1,
+        /// <summary>
+        ///  All complete queryables
+        /// </summary>
+        AllComplete = 2,
+    }
+
+    /// <summary>
+    ///  Reply consolidation mode
+    /// </summary>
+    internal enum ZenohConsolidationMode : uint
+    {
+        /// <summary>
+        ///  Let Zenoh choose depending on the selector
+        /// </summary>
+        Auto = 0,
+        /// <summary>
+        ///  No consolidation: every reply from every replica is delivered
+        /// </summary>
+        None = 1,
+        /// <summary>
+        ///  Only deliver replies newer than the last one received for the same key
+        /// </summary>
+        Monotonic = 2,
+        /// <summary>
+        ///  Only deliver the latest reply for each key
+        /// </summary>
+        Latest = 3,
+    }
+
     /// <summary>
     ///  Sample kind (Put or Delete)
     /// </summary>
@@ -364,6 +1846,9 @@ namespace ZenohDotNet.Native.FFI
         InvalidKeyExpr = 3,
         PutFailed = 4,
         NullPointer = 5,
+        InvalidEncoding = 6,
+        QueryFinalized = 7,
+        BufferTooSmall = 8,
         Panic = 254,
         Unknown = 255,
     }
@@ -0,0 +1,241 @@
+using System;
+using System.Runtime.InteropServices;
+using ZenohDotNet.Native.FFI;
+
+namespace ZenohDotNet.Native
+{
+    /// <summary>
+    /// A sample retained beyond its callback. The payload is shared with the received
+    /// sample rather than copied until it is read.
+    /// Dispose it to release the native sample.
+    /// </summary>
+    public sealed class OwnedSample : IDisposable
+    {
+        private unsafe ZenohOwnedSample* _handle;
+        private bool _disposed;
+
+        private unsafe OwnedSample(ZenohOwnedSample* handle)
+        {
+            _handle = handle;
+        }
+
+        /// <summary>
+        /// Retains a sample passed to a native callback. Must be called during the callback.
+        /// </summary>
+        internal static unsafe OwnedSample Clone(ZenohSampleRef* sample)
+        {
+            var handle = NativeMethods.zenoh_sample_clone(sample);
+            if (handle == null)
+                throw ZenohException.FromLastError("Failed to clone sample");
+            return new OwnedSample(handle);
+        }
+
+        /// <summary>
+        /// Creates another reference to the same native sample, e.g. to hand it to another
+        /// owner. Each reference must be disposed.
+        /// </summary>
+        public unsafe OwnedSample Retain()
+        {
+            ThrowIfDisposed();
+            return new OwnedSample(NativeMethods.zenoh_sample_retain(_handle));
+        }
+
+        /// <summary>
+        /// Gets the key expression of this sample.
+        /// </summary>
+        public unsafe string KeyExpression
+        {
+            get
+            {
+                ThrowIfDisposed();
+                nuint length;
+                var keyPtr = NativeMethods.zenoh_sample_key_expr(_handle, &length);
+                if (keyPtr == null)
+                    throw ZenohException.FromLastError("Failed to get sample key expression");
+                return System.Text.Encoding.UTF8.GetString(keyPtr, (int)length);
+            }
+        }
+
+        /// <summary>
+        /// Gets the total payload length in bytes.
+        /// </summary>
+        public unsafe int PayloadLength
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return (int)NativeMethods.zenoh_sample_payload_len(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Gets the kind of this sample (Put or Delete).
+        /// </summary>
+        public unsafe SampleKind Kind
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return (SampleKind)NativeMethods.zenoh_sample_kind(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Gets the encoding of this sample, ignoring any schema suffix.
+        /// </summary>
+        public unsafe PayloadEncoding Encoding
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return (PayloadEncoding)NativeMethods.zenoh_sample_encoding_id(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Gets the full encoding string of this sample, including any schema suffix.
+        /// </summary>
+        public unsafe string EncodingString
+        {
+            get
+            {
+                ThrowIfDisposed();
+                var encodingPtr = NativeMethods.zenoh_sample_encoding(_handle);
+                if (encodingPtr == null)
+                    throw ZenohException.FromLastError("Failed to get sample encoding");
+                try
+                {
+                    return Marshal.PtrToStringUTF8((IntPtr)encodingPtr) ?? string.Empty;
+                }
+                finally
+                {
+                    NativeMethods.zenoh_free_string(encodingPtr);
+                }
+            }
+        }
+
+        /// <summary>
+        /// Gets the timestamp of this sample (if available).
+        /// </summary>
+        public unsafe Timestamp? Timestamp
+        {
+            get
+            {
+                ThrowIfDisposed();
+                ZenohTimestamp timestamp;
+                if (!NativeMethods.zenoh_sample_timestamp(_handle, &timestamp))
+                    return null;
+
+                byte[] id = new byte[16];
+                for (int i = 0; i < 16; i++)
+                    id[i] = timestamp.id[i];
+                return new Timestamp(timestamp.time_ntp64, id);
+            }
+        }
+
+        /// <summary>
+        /// Gets the priority the sample was sent with.
+        /// </summary>
+        public unsafe Priority Priority
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return (Priority)NativeMethods.zenoh_sample_priority(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Gets the congestion control the sample was sent with.
+        /// </summary>
+        public unsafe CongestionControl CongestionControl
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return (CongestionControl)NativeMethods.zenoh_sample_congestion_control(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Gets whether the sample was sent with express (unbatched) delivery.
+        /// </summary>
+        public unsafe bool IsExpress
+        {
+            get
+            {
+                ThrowIfDisposed();
+                return NativeMethods.zenoh_sample_express(_handle);
+            }
+        }
+
+        /// <summary>
+        /// Copies the payload into a new array.
+        /// </summary>
+        public unsafe byte[] GetPayload()
+        {
+            ThrowIfDisposed();
+
+            var count = (int)NativeMethods.zenoh_sample_payload_slices(_handle, null, 0);
+            var slices = new ZenohPayloadSlice[count];
+            fixed (ZenohPayloadSlice* slicesPtr = slices)
+            {
+                NativeMethods.zenoh_sample_payload_slices(_handle, slicesPtr, (nuint)count);
+            }
+
+            var payload = new byte[PayloadLength];
+            int offset = 0;
+            foreach (var slice in slices)
+            {
+                new ReadOnlySpan<byte>(slice.data, (int)slice.len).CopyTo(payload.AsSpan(offset));
+                offset += (int)slice.len;
+            }
+            return payload;
+        }
+
+        /// <summary>
+        /// Copies the attachment into a new array, or returns null if the sample has none.
+        /// </summary>
+        public unsafe byte[]? GetAttachment()
+        {
+            ThrowIfDisposed();
+
+            nuint length;
+            var attachmentPtr = NativeMethods.zenoh_sample_attachment(_handle, &length);
+            if (attachmentPtr == null)
+                return null;
+            return new ReadOnlySpan<byte>(attachmentPtr, (int)length).ToArray();
+        }
+
+        private void ThrowIfDisposed()
+        {
+            if (_disposed)
+                throw new ObjectDisposedException(nameof(OwnedSample));
+        }
+
+        ~OwnedSample()
+        {
+            Dispose(false);
+        }
+
+        public void Dispose()
+        {
+            Dispose(true);
+            GC.SuppressFinalize(this);
+        }
+
+        private unsafe void Dispose(bool disposing)
+        {
+            if (!_disposed)
+            {
+                if (_handle != null)
+                {
+                    NativeMethods.zenoh_sample_drop(_handle);
+                    _handle = null;
+                }
+
+                _disposed = true;
+            }
+        }
+    }
+}
@@ -57,6 +57,8 @@ namespace ZenohDotNet.Native
                 if (queryPtr == null)
                     return;
 
+                // The query is finalized when the callback, or whoever it hands the
+                // query to, disposes it; deferred replies stay possible until then
                 var query = new Query(queryPtr);
                 _callback?.Invoke(query);
             }
@@ -105,17 +107,49 @@ namespace ZenohDotNet.Native
 
     /// <summary>
     /// Represents a query received by a queryable.
+    /// A query may be replied to any number of times, also after the queryable callback
+    /// has returned. It is finalized, signalling the requester that no more replies will
+    /// follow, when it is disposed, or when it is garbage collected if it never was;
+    /// dispose it once the last reply has been sent so the requester is not kept waiting.
     /// </summary>
-    public class Query
+    public class Query : IDisposable
     {
         private unsafe void* _handle;
-        private bool _replied;
 
         internal unsafe Query(void* handle)
         {
             _handle = handle;
         }
 
+        ~Query()
+        {
+            FinalizeNative();
+        }
+
+        /// <summary>
+        /// Finalizes the query. Further replies throw <see cref="ObjectDisposedException"/>.
+        /// </summary>
+        public void Dispose()
+        {
+            FinalizeNative();
+            GC.SuppressFinalize(this);
+        }
+
+        private unsafe void FinalizeNative()
+        {
+            if (_handle != null)
+            {
+                NativeMethods.zenoh_query_finalize(_handle);
+                _handle = null;
+            }
+        }
+
+        private unsafe void ThrowIfDisposed()
+        {
+            if (_handle == null)
+                throw new ObjectDisposedException(nameof(Query));
+        }
+
         /// <summary>
         /// Gets the selector (key expression) of the query.
         /// </summary>
@@ -123,6 +157,9 @@ namespace ZenohDotNet.Native
         {
             get
             {
+                if (_handle == null)
+                    return string.Empty;
+
                 byte* selectorPtr = NativeMethods.zenoh_query_selector(_handle);
                 if (selectorPtr == null)
                     return string.Empty;
@@ -143,8 +180,7 @@ namespace ZenohDotNet.Native
         /// </summary>
         public unsafe void Reply(string keyExpr, byte[] data)
         {
-            if (_replied)
-                throw new InvalidOperationException("Query has already been replied to");
+            ThrowIfDisposed();
 
             if (string.IsNullOrEmpty(keyExpr))
                 throw new ArgumentNullException(nameof(keyExpr));
@@ -163,8 +199,6 @@ namespace ZenohDotNet.Native
                     throw ZenohException.FromLastError("Failed to reply to query: error code {result}");
                 }
             }
-
-            _replied = true;
         }
 
         /// <summary>
@@ -1,4 +1,6 @@
 using System;
+using System.Runtime.InteropServices;
+using ZenohDotNet.Native.FFI;
 
 namespace ZenohDotNet.Native
 {
@@ -131,6 +133,35 @@ namespace ZenohDotNet.Native
             Timestamp = timestamp;
         }
 
+        /// <summary>
+        /// Copies a native sample, which is only valid during its callback, into a Sample.
+        /// </summary>
+        internal static unsafe Sample FromNative(SampleData* samplePtr)
+        {
+            string keyExpr = Marshal.PtrToStringUTF8((IntPtr)samplePtr->key_expr) ?? string.Empty;
+
+            int payloadLength = (int)samplePtr->payload_len;
+            byte[] payload = new byte[payloadLength];
+            if (payloadLength > 0)
+            {
+                Marshal.Copy((IntPtr)samplePtr->payload_data, payload, 0, payloadLength);
+            }
+
+            var kind = (SampleKind)samplePtr->kind;
+            var encoding = (PayloadEncoding)samplePtr->encoding_id;
+
+            Timestamp? timestamp = null;
+            if (samplePtr->timestamp_valid)
+            {
+                byte[] id = new byte[16];
+                for (int i = 0; i < 16; i++)
+                    id[i] = samplePtr->timestamp.id[i];
+                timestamp = new Timestamp(samplePtr->timestamp.time_ntp64, id);
+            }
+
+            return new Sample(keyExpr, payload, kind, encoding, timestamp);
+        }
+
         /// <summary>
         /// Gets the payload as a UTF-8 encoded string.
         /// </summary>
@@ -341,6 +341,39 @@ namespace ZenohDotNet.Native
             return new Subscriber(this, keyExpr, callback);
         }
 
+        /// <summary>
+        /// Creates a new subscriber whose callback receives each sample as an OwnedSample,
+        /// which shares the received payload instead of copying it and may be kept beyond
+        /// the callback. The callback owns the sample and must dispose it.
+        /// </summary>
+        public Subscriber DeclareOwnedSampleSubscriber(string keyExpr, Action<OwnedSample> callback)
+        {
+            if (string.IsNullOrEmpty(keyExpr))
+                throw new ArgumentNullException(nameof(keyExpr));
+            if (callback == null)
+                throw new ArgumentNullException(nameof(callback));
+
+            ThrowIfDisposed();
+            return new Subscriber(this, keyExpr, callback);
+        }
+
+        /// <summary>
+        /// Creates a new subscriber that delivers samples in batches.
+        /// A batch is delivered once it holds maxSamples samples, or maxDelay after its first
+        /// sample arrived, whichever comes first. Pass 0 or TimeSpan.Zero to disable either
+        /// bound; at least one must be set.
+        /// </summary>
+        public BatchSubscriber DeclareBatchSubscriber(string keyExpr, int maxSamples, TimeSpan maxDelay, Action<IReadOnlyList<Sample>> callback)
+        {
+            if (string.IsNullOrEmpty(keyExpr))
+                throw new ArgumentNullException(nameof(keyExpr));
+            if (callback == null)
+                throw new ArgumentNullException(nameof(callback));
+
+            ThrowIfDisposed();
+            return new BatchSubscriber(this, keyExpr, maxSamples, maxDelay, callback);
+        }
+
         /// <summary>
         /// Creates a new queryable for the specified key expression.
         /// </summary>
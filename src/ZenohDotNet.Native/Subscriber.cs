@@ -13,9 +13,11 @@ namespace ZenohDotNet.Native
         private unsafe void* _handle;
         private readonly Session _session;
         private readonly string _keyExpr;
-        private readonly Action<Sample> _callback;
+        private readonly Action<Sample>? _callback;
+        private readonly Action<OwnedSample>? _ownedCallback;
         private GCHandle _callbackHandle;
         private NativeMethods.zenoh_declare_subscriber_callback_delegate? _nativeCallback;
+        private NativeMethods.zenoh_declare_subscriber_view_callback_delegate? _nativeViewCallback;
         private bool _disposed;
 
         /// <summary>
@@ -23,25 +25,49 @@ namespace ZenohDotNet.Native
         /// </summary>
         public string KeyExpression => _keyExpr;
 
-        internal unsafe Subscriber(Session session, string keyExpr, Action<Sample> callback)
+        internal Subscriber(Session session, string keyExpr, Action<Sample> callback)
+            : this(session, keyExpr, callback ?? throw new ArgumentNullException(nameof(callback)), null)
+        {
+        }
+
+        internal Subscriber(Session session, string keyExpr, Action<OwnedSample> callback)
+            : this(session, keyExpr, null, callback ?? throw new ArgumentNullException(nameof(callback)))
+        {
+        }
+
+        private unsafe Subscriber(Session session, string keyExpr, Action<Sample>? callback, Action<OwnedSample>? ownedCallback)
         {
             _session = session ?? throw new ArgumentNullException(nameof(session));
             _keyExpr = keyExpr ?? throw new ArgumentNullException(nameof(keyExpr));
-            _callback = callback ?? throw new ArgumentNullException(nameof(callback));
+            _callback = callback;
+            _ownedCallback = ownedCallback;
 
             var keyBytes = Encoding.UTF8.GetBytes(keyExpr + "\0");
 
-            // Create native callback and prevent GC
-            _nativeCallback = OnSampleReceived;
-            _callbackHandle = GCHandle.Alloc(_nativeCallback);
-
             fixed (byte* keyPtr = keyBytes)
             {
-                _handle = NativeMethods.zenoh_declare_subscriber(
-                    session.Handle,
-                    keyPtr,
-                    _nativeCallback,
-                    null);
+                // Create native callback and prevent GC
+                if (ownedCallback != null)
+                {
+                    // The view path copies nothing per sample; the callback only takes a reference
+                    _nativeViewCallback = OnSampleViewReceived;
+                    _callbackHandle = GCHandle.Alloc(_nativeViewCallback);
+                    _handle = NativeMethods.zenoh_declare_subscriber_view(
+                        session.Handle,
+                        keyPtr,
+                        _nativeViewCallback,
+                        null);
+                }
+                else
+                {
+                    _nativeCallback = OnSampleReceived;
+                    _callbackHandle = GCHandle.Alloc(_nativeCallback);
+                    _handle = NativeMethods.zenoh_declare_subscriber(
+                        session.Handle,
+                        keyPtr,
+                        _nativeCallback,
+                        null);
+                }
             }
 
             if (_handle == null)
@@ -58,29 +84,23 @@ namespace ZenohDotNet.Native
                 if (samplePtr == null)
                     return;
 
-                string keyExpr = Marshal.PtrToStringUTF8((IntPtr)samplePtr->key_expr) ?? string.Empty;
-
-                int payloadLength = (int)samplePtr->payload_len;
-                byte[] payload = new byte[payloadLength];
-                if (payloadLength > 0)
-                {
-                    Marshal.Copy((IntPtr)samplePtr->payload_data, payload, 0, payloadLength);
-                }
-
-                var kind = (SampleKind)samplePtr->kind;
-                var encoding = (PayloadEncoding)samplePtr->encoding_id;
+                var sample = Sample.FromNative(samplePtr);
+                _callback?.Invoke(sample);
+            }
+            catch (Exception ex)
+            {
+                Console.Error.WriteLine($"Exception in subscriber callback: {ex}");
+            }
+        }
 
-                Timestamp? timestamp = null;
-                if (samplePtr->timestamp_valid)
-                {
-                    byte[] id = new byte[16];
-                    for (int i = 0; i < 16; i++)
-                        id[i] = samplePtr->timestamp.id[i];
-                    timestamp = new Timestamp(samplePtr->timestamp.time_ntp64, id);
-                }
+        private unsafe void OnSampleViewReceived(SampleView* viewPtr, void* contextPtr)
+        {
+            try
+            {
+                if (viewPtr == null)
+                    return;
 
-                var sample = new Sample(keyExpr, payload, kind, encoding, timestamp);
-                _callback?.Invoke(sample);
+                _ownedCallback?.Invoke(OwnedSample.Clone(viewPtr->sample));
             }
             catch (Exception ex)
             {
@@ -41,8 +41,12 @@ namespace ZenohDotNet.Unity
 
     /// <summary>
     /// Represents a query received by a queryable.
+    /// A query may be replied to several times, also after the queryable callback has
+    /// returned, which the callback being posted to the main thread relies on. It is
+    /// finalized when <see cref="Dispose"/> is called, or when it is garbage collected
+    /// if it never was; dispose it after the last reply.
     /// </summary>
-    public sealed class Query
+    public sealed class Query : IDisposable
     {
         private readonly ZenohDotNet.Native.Query _nativeQuery;
 
@@ -75,5 +79,13 @@ namespace ZenohDotNet.Unity
         {
             _nativeQuery.Reply(keyExpr, value);
         }
+
+        /// <summary>
+        /// Finalizes the query, signalling the requester that no more replies will follow.
+        /// </summary>
+        public void Dispose()
+        {
+            _nativeQuery.Dispose();
+        }
     }
 }
@@ -23,6 +23,7 @@ public class QueryIntegrationTests
         {
             queryReceived.TrySetResult(true);
             query.Reply(keyExpr, System.Text.Encoding.UTF8.GetBytes("Hello, Querier!"));
+            query.Dispose();
         });
 
         await Task.Delay(100);
@@ -51,6 +52,45 @@ public class QueryIntegrationTests
         Assert.Equal("Hello, Querier!", await replyReceived.Task);
     }
 
+    [Fact]
+    public async Task Queryable_RepliesAfterCallbackReturns()
+    {
+        // Arrange
+        var keyExpr = $"test/integration/query/deferred/{Guid.NewGuid()}";
+        var replyReceived = new TaskCompletionSource<string>();
+
+        await using var queryableSession = await Session.OpenAsync();
+        await using var querierSession = await Session.OpenAsync();
+
+        // Reply from another task once the callback has returned
+        await using var queryable = await queryableSession.DeclareQueryableAsync(keyExpr, query =>
+        {
+            _ = Task.Run(async () =>
+            {
+                await Task.Delay(50);
+                query.Reply(keyExpr, System.Text.Encoding.UTF8.GetBytes("Deferred"));
+                query.Dispose();
+            });
+        });
+
+        await Task.Delay(100);
+
+        // Act
+        await querierSession.GetAsync(keyExpr, sample =>
+        {
+            replyReceived.TrySetResult(System.Text.Encoding.UTF8.GetString(sample.Payload));
+        });
+
+        var gotReply = await Task.WhenAny(
+            replyReceived.Task,
+            Task.Delay(TimeSpan.FromSeconds(5))
+        ) == replyReceived.Task;
+
+        // Assert
+        Assert.True(gotReply, "Querier did not receive the deferred reply");
+        Assert.Equal("Deferred", await replyReceived.Task);
+    }
+
     [Fact]
     public async Task Queryable_CanAccessQuerySelector()
     {
@@ -66,6 +106,7 @@ public class QueryIntegrationTests
         {
             receivedSelector = query.Selector;
             query.Reply(keyExpr, System.Text.Encoding.UTF8.GetBytes("Acknowledged"));
+            query.Dispose();
             queryProcessed.TrySetResult(true);
         });
 
@@ -102,6 +143,7 @@ public class QueryIntegrationTests
                 receivedQueries.Add(query.Selector);
             }
             query.Reply(query.Selector, System.Text.Encoding.UTF8.GetBytes("OK"));
+            query.Dispose();
             if (receivedQueries.Count >= 2)
             {
                 queryCount.TrySetResult(true);
@@ -143,12 +185,14 @@ public class QueryIntegrationTests
         {
             queryable1Received.TrySetResult(true);
             query.Reply(keyExpr, System.Text.Encoding.UTF8.GetBytes("Reply from Q1"));
+            query.Dispose();
         });
 
         await using var queryable2 = await session2.DeclareQueryableAsync(keyExpr, query =>
         {
             queryable2Received.TrySetResult(true);
             query.Reply(keyExpr, System.Text.Encoding.UTF8.GetBytes("Reply from Q2"));
+            query.Dispose();
         });
 
         await Task.Delay(300); // Allow time for queryables to register
@@ -27,6 +27,7 @@ namespace ZenohDotNet.Native.Tests
             using var queryable = session.DeclareQueryable(keyExpr, query =>
             {
                 query.Reply(keyExpr, "Hello from queryable");
+                query.Dispose();
             });
             using var querier = session.DeclareQuerier(keyExpr);
 
@@ -54,6 +55,7 @@ namespace ZenohDotNet.Native.Tests
             {
                 queryCount++;
                 query.Reply(keyExpr, $"Reply {queryCount}");
+                query.Dispose();
             });
             using var querier = session.DeclareQuerier(keyExpr);
 